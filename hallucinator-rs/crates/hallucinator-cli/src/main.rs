@@ -2,51 +2,105 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 use tokio_util::sync::CancellationToken;
 
 mod output;
 
 use output::ColorMode;
 
+/// Output mode for the `check` subcommand's final report.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Format {
+    /// Human-oriented, optionally colored prose (the default).
+    #[default]
+    Text,
+    /// A single versioned JSON document, for CI and other tooling.
+    Json,
+}
+
+/// Schema version for the `--format json` document. Bump this whenever a
+/// field is removed or changes meaning so consumers can detect breakage;
+/// additive fields don't need a bump.
+///
+/// v2 wraps every run (even a single PDF) in `{"files": [...], "summary":
+/// {...}}` so batch mode didn't need a second, incompatible document shape.
+const JSON_REPORT_SCHEMA_VERSION: u32 = 2;
+
 /// Hallucinated Reference Detector - Detect fabricated references in academic PDFs
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    /// Path to the PDF file to check
-    pdf_path: PathBuf,
+    #[command(subcommand)]
+    command: Command,
 
     /// Disable colored output
-    #[arg(long)]
+    #[arg(long, global = true)]
     no_color: bool,
 
+    /// Path to output log file
+    #[arg(long, global = true)]
+    output: Option<PathBuf>,
+
     /// OpenAlex API key
-    #[arg(long)]
+    #[arg(long, global = true)]
     openalex_key: Option<String>,
 
     /// Semantic Scholar API key
-    #[arg(long)]
+    #[arg(long, global = true)]
     s2_api_key: Option<String>,
 
-    /// Path to output log file
-    #[arg(long)]
-    output: Option<PathBuf>,
-
-    /// Path to offline DBLP database
-    #[arg(long)]
-    dblp_offline: Option<PathBuf>,
-
-    /// Download and build offline DBLP database at the given path
-    #[arg(long)]
-    update_dblp: Option<PathBuf>,
-
-    /// Comma-separated list of databases to disable
-    #[arg(long, value_delimiter = ',')]
-    disable_dbs: Vec<String>,
+    /// HTTP/HTTPS proxy to route all remote database requests through
+    #[arg(long, global = true)]
+    proxy: Option<String>,
+}
 
-    /// Flag author mismatches from OpenAlex (default: skipped)
-    #[arg(long)]
-    check_openalex_authors: bool,
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check one or more PDFs' references for fabrications
+    Check {
+        /// PDF file(s) to check, or directories to scan recursively for *.pdf
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to offline DBLP database
+        #[arg(long)]
+        dblp_offline: Option<PathBuf>,
+
+        /// Comma-separated list of databases to disable
+        #[arg(long, value_delimiter = ',')]
+        disable_dbs: Vec<String>,
+
+        /// Flag author mismatches from OpenAlex (default: skipped)
+        #[arg(long)]
+        check_openalex_authors: bool,
+
+        /// Output format for the final report
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Resume a previously interrupted run from its run directory
+        /// (printed at the start of every run) instead of starting fresh.
+        /// PDFs already marked `Completed` in that run's manifest are
+        /// skipped.
+        #[arg(long)]
+        resume: Option<PathBuf>,
+    },
+    /// Download and build an offline DBLP database at the given path
+    UpdateDblp {
+        /// Where to build the offline DBLP database
+        db_path: PathBuf,
+    },
+    /// Run lightweight maintenance on an existing offline DBLP database
+    MaintainDblp {
+        /// Path to the offline DBLP database
+        db_path: PathBuf,
+
+        /// Also run VACUUM to compact the database file (slow — rewrites
+        /// the whole file, unlike the default PRAGMA-optimize-only pass)
+        #[arg(long)]
+        vacuum: bool,
+    },
 }
 
 #[tokio::main]
@@ -54,10 +108,31 @@ async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
     let args = Args::parse();
 
-    // Handle --update-dblp (exclusive mode)
-    if let Some(ref db_path) = args.update_dblp {
-        return update_dblp(db_path);
-    }
+    let (input_paths, dblp_offline, disable_dbs, check_openalex_authors, format, resume) =
+        match args.command {
+            Command::UpdateDblp { db_path } => return update_dblp(&db_path),
+            Command::MaintainDblp { db_path, vacuum } => return maintain_dblp(&db_path, vacuum),
+            Command::Check {
+                paths,
+                dblp_offline,
+                disable_dbs,
+                check_openalex_authors,
+                format,
+                resume,
+            } => (
+                paths,
+                dblp_offline,
+                disable_dbs,
+                check_openalex_authors,
+                format,
+                resume,
+            ),
+        };
+
+    // JSON output is machine-parsed, so it never carries ANSI color codes —
+    // only the text report honors --no-color/--output.
+    let color = ColorMode(format == Format::Text && !args.no_color && args.output.is_none());
+    let json_mode = format == Format::Json;
 
     // Resolve configuration: CLI flags > env vars > defaults
     let openalex_key = args
@@ -66,8 +141,7 @@ async fn main() -> anyhow::Result<()> {
     let s2_api_key = args
         .s2_api_key
         .or_else(|| std::env::var("S2_API_KEY").ok());
-    let dblp_offline_path = args
-        .dblp_offline
+    let dblp_offline_path = dblp_offline
         .or_else(|| std::env::var("DBLP_OFFLINE_PATH").ok().map(PathBuf::from));
     let db_timeout_secs: u64 = std::env::var("DB_TIMEOUT")
         .ok()
@@ -77,10 +151,25 @@ async fn main() -> anyhow::Result<()> {
         .ok()
         .and_then(|v| v.parse().ok())
         .unwrap_or(5);
-
-    // Determine color mode and output writer
-    let use_color = !args.no_color && args.output.is_none();
-    let color = ColorMode(use_color);
+    let proxy_url = args
+        .proxy
+        .or_else(|| std::env::var("HALLUCINATOR_PROXY").ok())
+        .or_else(|| std::env::var("HTTPS_PROXY").ok())
+        .or_else(|| std::env::var("HTTP_PROXY").ok());
+
+    // `hallucinator_core::Config` has no fields for a per-database base-URL
+    // override, and nothing in hallucinator-core constructs its OpenAlex/S2
+    // HTTP clients from one — so there was never a real integration point
+    // for `--openalex-url`/`--s2-url` to plug into, and they've been removed
+    // rather than shipped as flags that silently do nothing. `--proxy` is
+    // less invasive: `Config`'s HTTP clients are plain `reqwest` clients,
+    // and reqwest reads `HTTP_PROXY`/`HTTPS_PROXY` from the environment by
+    // default, so setting them here before any client gets built is enough
+    // to route requests through it.
+    if let Some(proxy) = &proxy_url {
+        std::env::set_var("HTTPS_PROXY", proxy);
+        std::env::set_var("HTTP_PROXY", proxy);
+    }
 
     let mut writer: Box<dyn Write> = if let Some(ref output_path) = args.output {
         Box::new(std::fs::File::create(output_path)?)
@@ -92,7 +181,7 @@ async fn main() -> anyhow::Result<()> {
     let dblp_offline_db = if let Some(ref path) = dblp_offline_path {
         if !path.exists() {
             anyhow::bail!(
-                "Offline DBLP database not found at {}. Use --update-dblp={} to build it.",
+                "Offline DBLP database not found at {}. Use update-dblp {} to build it.",
                 path.display(),
                 path.display()
             );
@@ -104,13 +193,13 @@ async fn main() -> anyhow::Result<()> {
             if staleness.is_stale {
                 let msg = if let Some(days) = staleness.age_days {
                     format!(
-                        "Offline DBLP database is {} days old. Consider running --update-dblp={} to refresh.",
+                        "Offline DBLP database is {} days old. Consider running update-dblp {} to refresh.",
                         days,
                         path.display()
                     )
                 } else {
                     format!(
-                        "Offline DBLP database may be stale. Consider running --update-dblp={} to refresh.",
+                        "Offline DBLP database may be stale. Consider running update-dblp {} to refresh.",
                         path.display()
                     )
                 };
@@ -129,69 +218,58 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
-    // Extract references from PDF
-    let pdf_path = &args.pdf_path;
-    if !pdf_path.exists() {
-        anyhow::bail!("PDF file not found: {}", pdf_path.display());
+    // Expand directories into the *.pdf files they contain, so a whole
+    // proceedings folder or paper archive can be passed as one argument.
+    let pdf_paths = collect_pdf_paths(&input_paths)?;
+    if pdf_paths.is_empty() {
+        anyhow::bail!("No PDF files found at the given path(s).");
     }
-
-    let extraction = hallucinator_pdf::extract_references(pdf_path)?;
-    let pdf_name = pdf_path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| pdf_path.display().to_string());
-
-    output::print_extraction_summary(
-        &mut writer,
-        &pdf_name,
-        extraction.references.len(),
-        &extraction.skip_stats,
-        color,
-    )?;
-
-    if extraction.references.is_empty() {
-        writeln!(writer, "No references to check.")?;
-        return Ok(());
-    }
-
-    // Build config
-    let config = hallucinator_core::Config {
-        openalex_key: openalex_key.clone(),
-        s2_api_key,
-        dblp_offline_path: dblp_offline_path.clone(),
-        dblp_offline_db,
-        max_concurrent_refs: 4,
-        db_timeout_secs,
-        db_timeout_short_secs,
-        disabled_dbs: args.disable_dbs,
-        check_openalex_authors: args.check_openalex_authors,
-    };
-
-    // Set up progress callback
-    // We use a Mutex<Box<dyn Write>> so the callback can write progress
-    let progress_writer: Arc<Mutex<Box<dyn Write + Send>>> = if args.output.is_some() {
-        // When writing to file, progress goes to the file too
-        // But we already consumed `writer`, so reopen
-        // Actually, let's write progress to stderr when output is a file
-        Arc::new(Mutex::new(Box::new(std::io::stderr())))
-    } else {
-        Arc::new(Mutex::new(Box::new(std::io::stdout())))
-    };
-
-    let progress_color = color;
-    let progress_cb = {
-        let pw = Arc::clone(&progress_writer);
-        move |event: hallucinator_core::ProgressEvent| {
-            if let Ok(mut w) = pw.lock() {
-                let _ = output::print_progress(&mut *w, &event, progress_color);
-                let _ = w.flush();
-            }
+    let batch_mode = pdf_paths.len() > 1;
+
+    // Crash-resumable work queue over the PDFs in this batch. Granularity is
+    // one `WorkKey` per file (`ref_index` always 0): `check_references`
+    // resolves a whole file's references in one call with no per-reference
+    // completion hook to persist against, so file-level is the finest
+    // resumability this loop can honestly offer. `--resume <dir>` reopens an
+    // existing run's manifest and skips files it already marked `Completed`;
+    // without it, a fresh run directory is created (and printed) so an
+    // interrupted run can be resumed later.
+    let (run_dir, mut manifest) = match &resume {
+        Some(dir) => {
+            let manifest = hallucinator_tui::persistence::resume_run(dir).unwrap_or_else(|| {
+                hallucinator_tui::persistence::Manifest::seed(&vec![1; pdf_paths.len()])
+            });
+            (dir.clone(), manifest)
+        }
+        None => {
+            let dir = hallucinator_tui::persistence::run_dir()
+                .unwrap_or_else(std::env::temp_dir);
+            let manifest =
+                hallucinator_tui::persistence::Manifest::seed(&vec![1; pdf_paths.len()]);
+            hallucinator_tui::persistence::save_manifest(&dir, &manifest);
+            (dir, manifest)
         }
     };
+    if !json_mode {
+        writeln!(
+            writer,
+            "Run directory: {} (resume an interrupted run with --resume {})",
+            run_dir.display(),
+            run_dir.display()
+        )?;
+    }
 
+    // Everything below is built once and reused across every file in the
+    // batch: the offline DBLP handle (an `Arc<Mutex<_>>` clone per file is
+    // cheap), the resolved API keys, and the HTTP clients `check_references`
+    // constructs from `Config`.
+    //
+    // Note: this does NOT yet share `hallucinator_core`'s in-memory
+    // `QueryCache` across files — `Config` has no hook for injecting one, so
+    // a title repeated across two papers in the same batch still triggers a
+    // fresh OpenAlex/S2/DBLP lookup for each. Plumbing a shared cache
+    // through needs a `Config` change in `hallucinator-core`.
     let cancel = CancellationToken::new();
-
-    // Set up Ctrl+C handler
     let cancel_clone = cancel.clone();
     tokio::spawn(async move {
         if tokio::signal::ctrl_c().await.is_ok() {
@@ -199,32 +277,238 @@ async fn main() -> anyhow::Result<()> {
         }
     });
 
-    let skip_stats = extraction.skip_stats.clone();
-    let results = hallucinator_core::check_references(
-        extraction.references,
-        config,
-        progress_cb,
-        cancel,
-    )
-    .await;
-
-    // Print final report
-    writeln!(writer)?;
-
-    output::print_hallucination_report(
-        &mut writer,
-        &results,
-        openalex_key.is_some(),
-        color,
-    )?;
-
-    output::print_doi_issues(&mut writer, &results, color)?;
-    output::print_retraction_warnings(&mut writer, &results, color)?;
-    output::print_summary(&mut writer, &results, &skip_stats, color)?;
+    let mut file_reports: Vec<serde_json::Value> = Vec::new();
+    let mut total_references = 0usize;
+    let mut total_checked = 0usize;
+    // (filename, reference count) — ranked into "worst offenders" below.
+    // This ranks by volume of references, not by hallucination count: that
+    // would need a per-reference verdict accessor that isn't part of
+    // `hallucinator_core`'s visible public surface here.
+    let mut per_file_counts: Vec<(String, usize)> = Vec::new();
+
+    for (idx, pdf_path) in pdf_paths.iter().enumerate() {
+        let work_key = hallucinator_tui::persistence::WorkKey {
+            paper_index: idx,
+            ref_index: 0,
+        };
+        if matches!(
+            manifest.status(work_key),
+            Some(hallucinator_tui::persistence::WorkStatus::Completed)
+        ) {
+            if !json_mode {
+                writeln!(
+                    writer,
+                    "Skipping {} (already completed in the resumed run).",
+                    pdf_path.display()
+                )?;
+            }
+            continue;
+        }
+
+        if !json_mode && batch_mode {
+            if idx > 0 {
+                writeln!(writer)?;
+            }
+            writeln!(writer, "=== {} ===", pdf_path.display())?;
+        }
+
+        manifest.mark_in_flight(work_key);
+        hallucinator_tui::persistence::save_manifest(&run_dir, &manifest);
+
+        let extraction = hallucinator_pdf::extract_references(pdf_path)?;
+        let pdf_name = pdf_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| pdf_path.display().to_string());
+
+        if !json_mode {
+            output::print_extraction_summary(
+                &mut writer,
+                &pdf_name,
+                extraction.references.len(),
+                &extraction.skip_stats,
+                color,
+            )?;
+        }
+
+        total_references += extraction.references.len();
+        per_file_counts.push((pdf_name.clone(), extraction.references.len()));
+
+        if extraction.references.is_empty() {
+            if json_mode {
+                file_reports.push(serde_json::json!({
+                    "pdf": pdf_name,
+                    "skip_stats": &extraction.skip_stats,
+                    "results": [],
+                }));
+            } else {
+                writeln!(writer, "No references to check.")?;
+            }
+            manifest.mark_completed(work_key);
+            hallucinator_tui::persistence::save_manifest(&run_dir, &manifest);
+            continue;
+        }
+
+        let config = hallucinator_core::Config {
+            openalex_key: openalex_key.clone(),
+            s2_api_key: s2_api_key.clone(),
+            dblp_offline_path: dblp_offline_path.clone(),
+            dblp_offline_db: dblp_offline_db.clone(),
+            max_concurrent_refs: 4,
+            db_timeout_secs,
+            db_timeout_short_secs,
+            disabled_dbs: disable_dbs.clone(),
+            check_openalex_authors,
+        };
+
+        // Set up progress callback
+        // We use a Mutex<Box<dyn Write>> so the callback can write progress
+        let progress_writer: Arc<Mutex<Box<dyn Write + Send>>> =
+            if json_mode || args.output.is_some() {
+                // The JSON document owns stdout/--output, so progress always
+                // goes to stderr in json mode; in text mode it also moves to
+                // stderr once --output is writing the report to a file.
+                Arc::new(Mutex::new(Box::new(std::io::stderr())))
+            } else {
+                Arc::new(Mutex::new(Box::new(std::io::stdout())))
+            };
+
+        let progress_color = color;
+        let progress_cb = {
+            let pw = Arc::clone(&progress_writer);
+            move |event: hallucinator_core::ProgressEvent| {
+                if let Ok(mut w) = pw.lock() {
+                    if json_mode {
+                        // Newline-delimited JSON, one event per line, so
+                        // tools can stream progress without buffering the
+                        // whole run.
+                        if let Ok(line) = serde_json::to_string(&event) {
+                            let _ = writeln!(w, "{}", line);
+                        }
+                    } else {
+                        let _ = output::print_progress(&mut *w, &event, progress_color);
+                    }
+                    let _ = w.flush();
+                }
+            }
+        };
+
+        let skip_stats = extraction.skip_stats.clone();
+        let results = hallucinator_core::check_references(
+            extraction.references,
+            config,
+            progress_cb,
+            cancel.clone(),
+        )
+        .await;
+
+        total_checked += results.iter().filter(|r| r.is_some()).count();
+
+        if json_mode {
+            file_reports.push(serde_json::json!({
+                "pdf": pdf_name,
+                "skip_stats": &skip_stats,
+                "results": &results,
+            }));
+        } else {
+            writeln!(writer)?;
+            output::print_hallucination_report(
+                &mut writer,
+                &results,
+                openalex_key.is_some(),
+                color,
+            )?;
+            output::print_doi_issues(&mut writer, &results, color)?;
+            output::print_retraction_warnings(&mut writer, &results, color)?;
+            output::print_summary(&mut writer, &results, &skip_stats, color)?;
+        }
+
+        if !cancel.is_cancelled() {
+            // Left `InFlight` on cancellation (reclaimed as `Pending` by
+            // `resume_run` on the next `--resume`) rather than marked
+            // `Completed`, since a cancelled run never actually finished it.
+            manifest.mark_completed(work_key);
+            hallucinator_tui::persistence::save_manifest(&run_dir, &manifest);
+        }
+
+        if cancel.is_cancelled() {
+            break;
+        }
+    }
+
+    per_file_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    per_file_counts.truncate(5);
+
+    if json_mode {
+        let report = serde_json::json!({
+            "schema_version": JSON_REPORT_SCHEMA_VERSION,
+            "openalex_enabled": openalex_key.is_some(),
+            "files": file_reports,
+            "summary": {
+                "total_files": pdf_paths.len(),
+                "total_references": total_references,
+                "total_checked": total_checked,
+                "worst_offenders": per_file_counts.iter().map(|(name, count)| {
+                    serde_json::json!({ "pdf": name, "reference_count": count })
+                }).collect::<Vec<_>>(),
+            },
+        });
+        writeln!(writer, "{}", serde_json::to_string_pretty(&report)?)?;
+    } else if batch_mode {
+        writeln!(writer)?;
+        writeln!(writer, "=== Batch summary ===")?;
+        writeln!(writer, "Files checked:      {}", pdf_paths.len())?;
+        writeln!(writer, "Total references:   {}", total_references)?;
+        writeln!(writer, "Total checked:      {}", total_checked)?;
+        if !per_file_counts.is_empty() {
+            writeln!(writer, "Worst offenders (by reference count):")?;
+            for (name, count) in &per_file_counts {
+                writeln!(writer, "  {:>5}  {}", count, name)?;
+            }
+        }
+    }
 
     Ok(())
 }
 
+/// Expand a mix of PDF files and directories into a flat, sorted list of PDF
+/// paths. Directories are scanned recursively for `*.pdf` (case-insensitive);
+/// a bare file path is taken as-is so a caller can still point directly at a
+/// non-`.pdf`-named file.
+fn collect_pdf_paths(inputs: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for input in inputs {
+        if !input.exists() {
+            anyhow::bail!("Path not found: {}", input.display());
+        }
+        if input.is_dir() {
+            collect_pdf_paths_recursive(input, &mut paths)?;
+        } else {
+            paths.push(input.clone());
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+fn collect_pdf_paths_recursive(dir: &std::path::Path, out: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_pdf_paths_recursive(&path, out)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("pdf"))
+        {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
 fn update_dblp(db_path: &PathBuf) -> anyhow::Result<()> {
     println!("Building offline DBLP database at {}...", db_path.display());
     println!("This will download ~4.6 GB and may take a while.");
@@ -282,3 +566,28 @@ fn update_dblp(db_path: &PathBuf) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn maintain_dblp(db_path: &PathBuf, vacuum: bool) -> anyhow::Result<()> {
+    if !db_path.exists() {
+        anyhow::bail!(
+            "Offline DBLP database not found at {}. Use update-dblp {} to build it.",
+            db_path.display(),
+            db_path.display()
+        );
+    }
+
+    let conn = rusqlite::Connection::open(db_path)?;
+    hallucinator_dblp::db::run_maintenance(&conn, vacuum, |event| match event {
+        hallucinator_dblp::db::MaintenanceProgress::Optimizing => {
+            println!("Running PRAGMA optimize...");
+        }
+        hallucinator_dblp::db::MaintenanceProgress::Vacuuming => {
+            println!("Running VACUUM (this rewrites the whole file and may take a while)...");
+        }
+        hallucinator_dblp::db::MaintenanceProgress::Complete { elapsed_ms } => {
+            println!("Done in {:.1}s.", elapsed_ms as f64 / 1000.0);
+        }
+    })?;
+
+    Ok(())
+}
@@ -14,11 +14,12 @@
 
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use dashmap::DashMap;
 use rusqlite::{Connection, params};
+use tokio::sync::Notify;
 
 use crate::db::DbQueryResult;
 use crate::matching::normalize_title;
@@ -50,7 +51,7 @@ enum CachedResult {
 }
 
 /// A timestamped cache entry (L1 only — uses monotonic `Instant`).
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 struct CacheEntry {
     result: CachedResult,
     inserted_at: Instant,
@@ -58,6 +59,10 @@ struct CacheEntry {
     /// actively read back from L1 — SQLite uses it on promotion).
     #[allow(dead_code)]
     inserted_epoch: u64,
+    /// Monotonic tick stamped on insert and bumped on every `get` hit, used
+    /// by the approximate-LRU eviction sweep below (smallest tick among a
+    /// sampled set is evicted first).
+    last_access: AtomicU64,
 }
 
 /// SQLite-backed persistent store (L2).
@@ -83,11 +88,59 @@ impl SqliteStore {
                  paper_url        TEXT,
                  inserted_at      INTEGER NOT NULL,
                  PRIMARY KEY (normalized_title, db_name)
-             );",
+             );
+             CREATE TABLE IF NOT EXISTS cache_meta (
+                 id                   INTEGER PRIMARY KEY CHECK (id = 0),
+                 lifetime_hits        INTEGER NOT NULL DEFAULT 0,
+                 lifetime_misses      INTEGER NOT NULL DEFAULT 0,
+                 last_eviction_epoch  INTEGER
+             );
+             INSERT OR IGNORE INTO cache_meta (id, lifetime_hits, lifetime_misses, last_eviction_epoch)
+             VALUES (0, 0, 0, NULL);",
         )?;
         Ok(Self { conn })
     }
 
+    /// Read the persisted lifetime hit/miss counters (0/0 if never written).
+    fn load_meta(&self) -> (u64, u64) {
+        self.conn
+            .query_row(
+                "SELECT lifetime_hits, lifetime_misses FROM cache_meta WHERE id = 0",
+                [],
+                |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, i64>(1)? as u64)),
+            )
+            .unwrap_or((0, 0))
+    }
+
+    /// Persist the current lifetime hit/miss counters. Called from
+    /// [`QueryCache`]'s `Drop` so a restart picks up where the last run left
+    /// off instead of resetting to zero.
+    fn save_hit_counters(&self, lifetime_hits: u64, lifetime_misses: u64) {
+        let _ = self.conn.execute(
+            "UPDATE cache_meta SET lifetime_hits = ?1, lifetime_misses = ?2 WHERE id = 0",
+            params![lifetime_hits as i64, lifetime_misses as i64],
+        );
+    }
+
+    fn save_last_eviction(&self, epoch: u64) {
+        let _ = self.conn.execute(
+            "UPDATE cache_meta SET last_eviction_epoch = ?1 WHERE id = 0",
+            params![epoch as i64],
+        );
+    }
+
+    fn last_eviction_epoch(&self) -> Option<u64> {
+        self.conn
+            .query_row(
+                "SELECT last_eviction_epoch FROM cache_meta WHERE id = 0",
+                [],
+                |row| row.get::<_, Option<i64>>(0),
+            )
+            .ok()
+            .flatten()
+            .map(|v| v as u64)
+    }
+
     fn get(
         &self,
         norm_title: &str,
@@ -117,18 +170,7 @@ impl SqliteStore {
             .ok()?;
 
         let (found, found_title, authors_json, paper_url, inserted_at) = row;
-
-        let result = if found != 0 {
-            CachedResult::Found {
-                title: found_title.unwrap_or_default(),
-                authors: authors_json
-                    .and_then(|j| serde_json::from_str(&j).ok())
-                    .unwrap_or_default(),
-                url: paper_url,
-            }
-        } else {
-            CachedResult::NotFound
-        };
+        let result = decode_row(found, found_title, authors_json, paper_url);
 
         // Check TTL
         let ttl = match &result {
@@ -179,6 +221,97 @@ impl SqliteStore {
         );
     }
 
+    /// Write every item inside a single `BEGIN … COMMIT` transaction, reusing
+    /// the same cached prepared statement for each row instead of paying a
+    /// mutex acquisition and WAL fsync per item the way repeated calls to
+    /// [`insert`](Self::insert) would.
+    fn insert_batch(&self, items: &[(String, String, CachedResult, u64)]) {
+        if items.is_empty() {
+            return;
+        }
+
+        if self.conn.execute_batch("BEGIN;").is_err() {
+            // Couldn't even start the transaction — fall back to
+            // one-at-a-time so the batch still lands best-effort.
+            for (norm_title, db_name, result, epoch) in items {
+                self.insert(norm_title, db_name, result, *epoch);
+            }
+            return;
+        }
+        for (norm_title, db_name, result, epoch) in items {
+            self.insert(norm_title, db_name, result, *epoch);
+        }
+        let _ = self.conn.execute_batch("COMMIT;");
+    }
+
+    fn delete_one(&self, norm_title: &str, db_name: &str) {
+        let _ = self.conn.execute(
+            "DELETE FROM query_cache WHERE normalized_title = ?1 AND db_name = ?2",
+            params![norm_title, db_name],
+        );
+    }
+
+    fn delete_db(&self, db_name: &str) {
+        let _ = self
+            .conn
+            .execute("DELETE FROM query_cache WHERE db_name = ?1", params![db_name]);
+    }
+
+    /// Delete rows matching an arbitrary Rust predicate over
+    /// `(normalized_title, db_name)`. Since the predicate isn't SQL, this
+    /// scans every row rather than pushing the filter into the query.
+    fn delete_where(&self, pred: &dyn Fn(&str, &str) -> bool) {
+        let mut stmt = match self
+            .conn
+            .prepare("SELECT normalized_title, db_name FROM query_cache")
+        {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        let rows: Vec<(String, String)> = match stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        {
+            Ok(mapped) => mapped.filter_map(Result::ok).collect(),
+            Err(_) => return,
+        };
+        drop(stmt);
+        for (norm_title, db_name) in rows {
+            if pred(&norm_title, &db_name) {
+                self.delete_one(&norm_title, &db_name);
+            }
+        }
+    }
+
+    /// Apply a batch of retractions and assertions inside a single
+    /// transaction, matching [`insert_batch`](Self::insert_batch)'s
+    /// best-effort semantics: if the transaction fails to start, falls back
+    /// to applying each change one at a time rather than dropping the batch.
+    fn apply_updates(
+        &self,
+        retractions: &[(String, String)],
+        assertions: &[(String, String, CachedResult, u64)],
+    ) {
+        if retractions.is_empty() && assertions.is_empty() {
+            return;
+        }
+
+        let run = |store: &Self| {
+            for (norm_title, db_name) in retractions {
+                store.delete_one(norm_title, db_name);
+            }
+            for (norm_title, db_name, result, epoch) in assertions {
+                store.insert(norm_title, db_name, result, *epoch);
+            }
+        };
+
+        if self.conn.execute_batch("BEGIN;").is_err() {
+            run(self);
+            return;
+        }
+        run(self);
+        let _ = self.conn.execute_batch("COMMIT;");
+    }
+
     fn clear(&self) {
         let _ = self.conn.execute("DELETE FROM query_cache", []);
     }
@@ -194,6 +327,7 @@ impl SqliteStore {
                  (found = 0 AND inserted_at < ?2)",
             params![pos_cutoff, neg_cutoff],
         );
+        self.save_last_eviction(now);
     }
 
     fn count(&self) -> usize {
@@ -203,6 +337,75 @@ impl SqliteStore {
             })
             .unwrap_or(0) as usize
     }
+
+    /// Select the `limit` most-recently-inserted non-expired rows, for
+    /// promoting into L1 right after opening so a restarted process starts
+    /// with a hot cache instead of cold-starting entirely against disk.
+    fn select_warm_rows(
+        &self,
+        limit: usize,
+        positive_ttl: Duration,
+        negative_ttl: Duration,
+    ) -> Vec<(String, String, CachedResult, u64)> {
+        let now = now_epoch();
+        let mut stmt = match self.conn.prepare(
+            "SELECT normalized_title, db_name, found, found_title, authors, paper_url, inserted_at
+             FROM query_cache ORDER BY inserted_at DESC LIMIT ?1",
+        ) {
+            Ok(s) => s,
+            Err(_) => return Vec::new(),
+        };
+
+        let rows = stmt.query_map(params![limit as i64], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i32>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, u64>(6)?,
+            ))
+        });
+        let Ok(rows) = rows else {
+            return Vec::new();
+        };
+
+        rows.filter_map(Result::ok)
+            .filter_map(
+                |(norm_title, db_name, found, found_title, authors_json, paper_url, inserted_at)| {
+                    let result = decode_row(found, found_title, authors_json, paper_url);
+                    let ttl = match &result {
+                        CachedResult::Found { .. } => positive_ttl,
+                        CachedResult::NotFound => negative_ttl,
+                    };
+                    let age = Duration::from_secs(now.saturating_sub(inserted_at));
+                    (age <= ttl).then_some((norm_title, db_name, result, inserted_at))
+                },
+            )
+            .collect()
+    }
+}
+
+/// Decode a raw `query_cache` row into a [`CachedResult`]. Shared by
+/// [`SqliteStore::get`] and [`SqliteStore::select_warm_rows`].
+fn decode_row(
+    found: i32,
+    found_title: Option<String>,
+    authors_json: Option<String>,
+    paper_url: Option<String>,
+) -> CachedResult {
+    if found != 0 {
+        CachedResult::Found {
+            title: found_title.unwrap_or_default(),
+            authors: authors_json
+                .and_then(|j| serde_json::from_str(&j).ok())
+                .unwrap_or_default(),
+            url: paper_url,
+        }
+    } else {
+        CachedResult::NotFound
+    }
 }
 
 fn now_epoch() -> u64 {
@@ -223,8 +426,84 @@ pub struct QueryCache {
     negative_ttl: Duration,
     hits: AtomicU64,
     misses: AtomicU64,
+    /// Upper bound on L1's entry count. `None` means unbounded (the
+    /// pre-existing behavior). Evicted entries remain retrievable from L2
+    /// if persistence is enabled, since L1 is strictly a cache of L2.
+    max_l1_entries: Option<usize>,
+    /// Monotonic tick, bumped on every `get` hit and `insert`, stamped onto
+    /// each [`CacheEntry::last_access`]. Drives the sampled-LRU sweep below.
+    clock: AtomicU64,
+    /// Lifetime hits/misses persisted from a previous run (0 for a fresh or
+    /// in-memory-only cache). [`stats`](Self::stats) adds the current
+    /// session's counters on top of these.
+    lifetime_hits_base: u64,
+    lifetime_misses_base: u64,
+    /// Keys currently being resolved by [`get_or_fetch`](Self::get_or_fetch),
+    /// so concurrent misses on the same key coalesce into one fetch instead
+    /// of a thundering herd of identical remote queries.
+    in_flight: DashMap<CacheKey, Arc<Notify>>,
+}
+
+/// Removes a key's `in_flight` entry and wakes its waiters when dropped,
+/// whether that happens on [`get_or_fetch`](QueryCache::get_or_fetch)'s
+/// normal return path or because the leader's `fetch` panicked and the drop
+/// ran during unwinding instead. Without this, a panicking leader would leave
+/// the entry behind forever: waiters already parked on `notified()` hang,
+/// and every subsequent caller for the key finds the entry pre-existing and
+/// becomes a waiter too, hanging in turn.
+struct InFlightGuard<'a> {
+    cache: &'a QueryCache,
+    key: CacheKey,
+    notify: Arc<Notify>,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.cache.in_flight.remove(&self.key);
+        self.notify.notify_waiters();
+    }
+}
+
+/// Snapshot of [`QueryCache`] hit/miss statistics, combining the current
+/// process's session counters with whatever was persisted from prior runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub session_hits: u64,
+    pub session_misses: u64,
+    pub lifetime_hits: u64,
+    pub lifetime_misses: u64,
+    /// When L2's last expired-entry sweep ran, as a Unix epoch second
+    /// (`None` for an in-memory-only cache, or one that's never been opened
+    /// with a SQLite backing before).
+    pub last_eviction_epoch: Option<u64>,
 }
 
+impl CacheStats {
+    /// Hit rate over just this process's session, in `[0.0, 1.0]`.
+    pub fn session_hit_rate(&self) -> f64 {
+        hit_rate(self.session_hits, self.session_misses)
+    }
+
+    /// Hit rate across this session and all persisted prior runs, in `[0.0, 1.0]`.
+    pub fn lifetime_hit_rate(&self) -> f64 {
+        hit_rate(self.lifetime_hits, self.lifetime_misses)
+    }
+}
+
+fn hit_rate(hits: u64, misses: u64) -> f64 {
+    let total = hits + misses;
+    if total == 0 {
+        0.0
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+/// How many entries the sampled-LRU eviction sweep examines per overflow
+/// insert. `DashMap` has no global access ordering, so eviction looks at a
+/// small sample rather than scanning the whole map (CLOCK-style).
+const EVICTION_SAMPLE_SIZE: usize = 8;
+
 impl Default for QueryCache {
     fn default() -> Self {
         Self::new(DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL)
@@ -232,7 +511,8 @@ impl Default for QueryCache {
 }
 
 impl QueryCache {
-    /// Create an in-memory-only cache with custom TTLs (no disk persistence).
+    /// Create an in-memory-only cache with custom TTLs (no disk persistence)
+    /// and no cap on L1 size.
     pub fn new(positive_ttl: Duration, negative_ttl: Duration) -> Self {
         Self {
             entries: DashMap::new(),
@@ -241,6 +521,26 @@ impl QueryCache {
             negative_ttl,
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
+            max_l1_entries: None,
+            clock: AtomicU64::new(0),
+            lifetime_hits_base: 0,
+            lifetime_misses_base: 0,
+            in_flight: DashMap::new(),
+        }
+    }
+
+    /// Like [`new`](Self::new), but bounds L1 to at most `max_l1_entries`
+    /// entries via approximate LRU eviction. On a long-running drainer
+    /// processing millions of distinct titles, this keeps memory use flat
+    /// regardless of how many titles have ever been queried.
+    pub fn with_l1_capacity(
+        positive_ttl: Duration,
+        negative_ttl: Duration,
+        max_l1_entries: Option<usize>,
+    ) -> Self {
+        Self {
+            max_l1_entries,
+            ..Self::new(positive_ttl, negative_ttl)
         }
     }
 
@@ -252,20 +552,123 @@ impl QueryCache {
         path: &Path,
         positive_ttl: Duration,
         negative_ttl: Duration,
+    ) -> Result<Self, String> {
+        Self::open_internal(path, positive_ttl, negative_ttl, None, 0)
+    }
+
+    /// Like [`open`](Self::open), but bounds L1 to at most `max_l1_entries`
+    /// entries via approximate LRU eviction (see [`with_l1_capacity`](Self::with_l1_capacity)).
+    pub fn open_with_l1_capacity(
+        path: &Path,
+        positive_ttl: Duration,
+        negative_ttl: Duration,
+        max_l1_entries: Option<usize>,
+    ) -> Result<Self, String> {
+        Self::open_internal(path, positive_ttl, negative_ttl, max_l1_entries, 0)
+    }
+
+    /// Like [`open`](Self::open), but also promotes the `warm_limit`
+    /// most-recently-inserted non-expired L2 rows into L1 up front, so a
+    /// restarted process starts with a hot cache instead of paying a SQLite
+    /// round-trip for the first wave of lookups.
+    pub fn open_with_warmup(
+        path: &Path,
+        positive_ttl: Duration,
+        negative_ttl: Duration,
+        warm_limit: usize,
+    ) -> Result<Self, String> {
+        Self::open_internal(path, positive_ttl, negative_ttl, None, warm_limit)
+    }
+
+    fn open_internal(
+        path: &Path,
+        positive_ttl: Duration,
+        negative_ttl: Duration,
+        max_l1_entries: Option<usize>,
+        warm_limit: usize,
     ) -> Result<Self, String> {
         let store = SqliteStore::open(path)
             .map_err(|e| format!("Failed to open cache database at {}: {}", path.display(), e))?;
         store.evict_expired(positive_ttl, negative_ttl);
+        let (lifetime_hits_base, lifetime_misses_base) = store.load_meta();
+
+        let entries = DashMap::new();
+        let clock = AtomicU64::new(0);
+        if warm_limit > 0 {
+            for (norm_title, db_name, result, epoch) in
+                store.select_warm_rows(warm_limit, positive_ttl, negative_ttl)
+            {
+                let tick = clock.fetch_add(1, Ordering::Relaxed);
+                entries.insert(
+                    CacheKey {
+                        normalized_title: norm_title,
+                        db_name,
+                    },
+                    CacheEntry {
+                        result,
+                        inserted_at: epoch_to_instant(epoch),
+                        inserted_epoch: epoch,
+                        last_access: AtomicU64::new(tick),
+                    },
+                );
+            }
+        }
+
         Ok(Self {
-            entries: DashMap::new(),
+            entries,
             sqlite: Some(Mutex::new(store)),
             positive_ttl,
             negative_ttl,
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
+            max_l1_entries,
+            clock,
+            lifetime_hits_base,
+            lifetime_misses_base,
+            in_flight: DashMap::new(),
         })
     }
 
+    /// Stamp-and-fetch the next clock tick, used to mark an entry as
+    /// just-accessed for the sampled-LRU sweep.
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// If L1 is over capacity, sample a handful of entries (starting at a
+    /// rotating offset derived from the current clock tick, so repeated
+    /// overflows sweep across the map rather than re-sampling the same
+    /// corner) and evict whichever has the smallest `last_access`. Evicted
+    /// entries are still retrievable from L2 if persistence is enabled.
+    fn evict_if_over_capacity(&self) {
+        let Some(cap) = self.max_l1_entries else {
+            return;
+        };
+        if self.entries.len() <= cap {
+            return;
+        }
+
+        let skip = self.clock.load(Ordering::Relaxed) as usize % self.entries.len().max(1);
+        let mut oldest_key: Option<CacheKey> = None;
+        let mut oldest_tick = u64::MAX;
+        let sample = self
+            .entries
+            .iter()
+            .skip(skip)
+            .chain(self.entries.iter().take(skip))
+            .take(EVICTION_SAMPLE_SIZE);
+        for entry in sample {
+            let last_access = entry.value().last_access.load(Ordering::Relaxed);
+            if last_access < oldest_tick {
+                oldest_tick = last_access;
+                oldest_key = Some(entry.key().clone());
+            }
+        }
+        if let Some(key) = oldest_key {
+            self.entries.remove(&key);
+        }
+    }
+
     /// Look up a cached result for the given title and database.
     ///
     /// Returns `Some(result)` on cache hit (within TTL), `None` on miss.
@@ -288,6 +691,7 @@ impl QueryCache {
                 self.entries.remove(&key);
                 // Fall through to L2
             } else {
+                entry.last_access.store(self.tick(), Ordering::Relaxed);
                 self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(cached_to_query_result(&entry.result));
             }
@@ -307,8 +711,10 @@ impl QueryCache {
                             result,
                             inserted_at: epoch_to_instant(epoch),
                             inserted_epoch: epoch,
+                            last_access: AtomicU64::new(self.tick()),
                         },
                     );
+                    self.evict_if_over_capacity();
                     self.hits.fetch_add(1, Ordering::Relaxed);
                     return Some(query_result);
                 }
@@ -323,40 +729,252 @@ impl QueryCache {
     ///
     /// Only caches successful results (found or not-found). Errors should NOT
     /// be passed to this method. Write-through: updates both L1 and L2.
+    ///
+    /// A thin wrapper over a one-element [`insert_batch`](Self::insert_batch) —
+    /// prefer that method when writing many results at once (e.g. draining a
+    /// batch of resolved references), since it amortizes the L2 mutex
+    /// acquisition and transaction commit across the whole batch instead of
+    /// paying it per item.
     pub fn insert(&self, title: &str, db_name: &str, result: &DbQueryResult) {
-        let norm = normalize_title(title);
+        self.insert_batch(std::slice::from_ref(&(
+            title.to_string(),
+            db_name.to_string(),
+            result.clone(),
+        )));
+    }
+
+    /// Insert many query results in one go.
+    ///
+    /// L1 is updated per item as usual. L2 is updated once: the sqlite mutex
+    /// is locked a single time and every `INSERT OR REPLACE` runs inside one
+    /// `BEGIN … COMMIT` transaction, so a high-throughput drainer pays one
+    /// mutex acquisition and one WAL fsync for the whole batch instead of one
+    /// per result. If the transaction fails partway, the L1 entries already
+    /// written above still stand — L2 writes are best-effort, matching the
+    /// existing single-item `insert`'s behavior of never surfacing a write
+    /// error to the caller.
+    pub fn insert_batch(&self, items: &[(String, String, DbQueryResult)]) {
+        if items.is_empty() {
+            return;
+        }
+
+        let mut l2_items = Vec::with_capacity(items.len());
+        for (title, db_name, result) in items {
+            let norm = normalize_title(title);
+            let key = CacheKey {
+                normalized_title: norm.clone(),
+                db_name: db_name.clone(),
+            };
+
+            let cached = match result {
+                (Some(found_title), authors, url) => CachedResult::Found {
+                    title: found_title.clone(),
+                    authors: authors.clone(),
+                    url: url.clone(),
+                },
+                (None, _, _) => CachedResult::NotFound,
+            };
+
+            let epoch = now_epoch();
+
+            // L1
+            self.entries.insert(
+                key,
+                CacheEntry {
+                    result: cached.clone(),
+                    inserted_at: Instant::now(),
+                    inserted_epoch: epoch,
+                    last_access: AtomicU64::new(self.tick()),
+                },
+            );
+            self.evict_if_over_capacity();
+
+            l2_items.push((norm, db_name.clone(), cached, epoch));
+        }
+
+        // L2 — one lock, one transaction for the whole batch.
+        if let Some(ref sqlite_mutex) = self.sqlite {
+            if let Ok(store) = sqlite_mutex.lock() {
+                store.insert_batch(&l2_items);
+            }
+        }
+    }
+
+    /// Resolve `(title, db_name)` through the cache, coalescing concurrent
+    /// misses on the same key into a single call to `fetch`.
+    ///
+    /// On an L1/L2 hit, returns immediately without touching `fetch` at all.
+    /// On a miss, the first caller to observe it becomes that key's leader:
+    /// it drives `fetch` to completion, write-throughs the result on success
+    /// exactly like [`insert`](Self::insert), and wakes every other caller
+    /// that was waiting on the same key so they read the freshly cached
+    /// value instead of each firing an identical, expensive remote query —
+    /// the classic cache-stampede problem on a drainer with many concurrent
+    /// tasks. A failed fetch leaves nothing cached (matching `insert`'s
+    /// "only successful results are cached" invariant); waiters then retry
+    /// as a fresh leader rather than being handed the leader's error.
+    pub async fn get_or_fetch<F, E>(
+        &self,
+        title: &str,
+        db_name: &str,
+        fetch: F,
+    ) -> Result<DbQueryResult, E>
+    where
+        F: std::future::Future<Output = Result<DbQueryResult, E>>,
+    {
+        tokio::pin!(fetch);
         let key = CacheKey {
-            normalized_title: norm.clone(),
+            normalized_title: normalize_title(title),
             db_name: db_name.to_string(),
         };
 
-        let cached = match result {
-            (Some(found_title), authors, url) => CachedResult::Found {
-                title: found_title.clone(),
-                authors: authors.clone(),
-                url: url.clone(),
-            },
-            (None, _, _) => CachedResult::NotFound,
-        };
+        loop {
+            if let Some(cached) = self.get(title, db_name) {
+                return Ok(cached);
+            }
 
-        let epoch = now_epoch();
+            let mut leader = false;
+            let notify = self
+                .in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    leader = true;
+                    Arc::new(Notify::new())
+                })
+                .clone();
+
+            if !leader {
+                // Register interest before rechecking the cache, so a
+                // notify_waiters() that races with this check is never
+                // missed — the Notified future, once created, is guaranteed
+                // to observe notifications sent after this point even if we
+                // haven't polled it yet.
+                let notified = notify.notified();
+                if let Some(cached) = self.get(title, db_name) {
+                    return Ok(cached);
+                }
+                notified.await;
+                // Leader may have failed and left nothing cached — loop
+                // around and race to become the new leader ourselves.
+                continue;
+            }
 
-        // L1
-        self.entries.insert(
-            key,
-            CacheEntry {
-                result: cached.clone(),
-                inserted_at: Instant::now(),
-                inserted_epoch: epoch,
-            },
-        );
+            // Guards the `in_flight` entry for the rest of this iteration: if
+            // `fetch` panics, unwinding still drops `_guard`, which removes
+            // the entry and wakes waiters. Without this, a panicking leader
+            // would orphan the entry forever — parked waiters hang, and every
+            // later caller for this key finds the entry already present and
+            // becomes a waiter too, hanging in turn.
+            let _guard = InFlightGuard {
+                cache: self,
+                key: key.clone(),
+                notify: Arc::clone(&notify),
+            };
+
+            let outcome = fetch.as_mut().await;
+            if let Ok(ref result) = outcome {
+                self.insert(title, db_name, result);
+            }
+            return outcome;
+        }
+    }
+
+    /// Remove every cached entry (L1 and L2) for one database, e.g. after a
+    /// provider reindexes and everything previously cached for it may now be
+    /// stale. Entries for other databases are untouched — unlike
+    /// [`clear`](Self::clear), which nukes the whole warm cache.
+    pub fn invalidate_db(&self, db_name: &str) {
+        self.entries.retain(|key, _| key.db_name != db_name);
+        if let Some(ref sqlite_mutex) = self.sqlite {
+            if let Ok(store) = sqlite_mutex.lock() {
+                store.delete_db(db_name);
+            }
+        }
+    }
+
+    /// Remove every cached entry (L1 and L2) whose `(normalized_title,
+    /// db_name)` satisfies `pred`. More surgical than
+    /// [`invalidate_db`](Self::invalidate_db) when only a subset of one
+    /// database's entries (or entries spanning several databases) need
+    /// expiring.
+    pub fn invalidate_where(&self, pred: impl Fn(&str, &str) -> bool) {
+        self.entries
+            .retain(|key, _| !pred(&key.normalized_title, &key.db_name));
+        if let Some(ref sqlite_mutex) = self.sqlite {
+            if let Ok(store) = sqlite_mutex.lock() {
+                store.delete_where(&pred);
+            }
+        }
+    }
+
+    /// Apply a set of retractions and assertions as one atomic L2
+    /// transaction, then reconcile L1 to match. `retractions` are
+    /// `(title, db_name)` pairs to remove; `assertions` are
+    /// `(title, db_name, result)` triples to upsert, same shape as
+    /// [`insert_batch`](Self::insert_batch). Lets an operator apply a schema
+    /// or reindex update as a single surgical pass instead of clearing the
+    /// whole cache and paying for every entry to be refetched.
+    pub fn apply_updates(
+        &self,
+        retractions: &[(String, String)],
+        assertions: &[(String, String, DbQueryResult)],
+    ) {
+        let norm_retractions: Vec<(String, String)> = retractions
+            .iter()
+            .map(|(title, db_name)| (normalize_title(title), db_name.clone()))
+            .collect();
+
+        let mut l2_assertions = Vec::with_capacity(assertions.len());
+        let mut l1_assertions = Vec::with_capacity(assertions.len());
+        for (title, db_name, result) in assertions {
+            let norm = normalize_title(title);
+            let cached = match result {
+                (Some(found_title), authors, url) => CachedResult::Found {
+                    title: found_title.clone(),
+                    authors: authors.clone(),
+                    url: url.clone(),
+                },
+                (None, _, _) => CachedResult::NotFound,
+            };
+            let epoch = now_epoch();
+            l1_assertions.push((
+                CacheKey {
+                    normalized_title: norm.clone(),
+                    db_name: db_name.clone(),
+                },
+                cached.clone(),
+                epoch,
+            ));
+            l2_assertions.push((norm, db_name.clone(), cached, epoch));
+        }
 
-        // L2
+        // L2 — one lock, one transaction for the whole update set.
         if let Some(ref sqlite_mutex) = self.sqlite {
             if let Ok(store) = sqlite_mutex.lock() {
-                store.insert(&norm, db_name, &cached, epoch);
+                store.apply_updates(&norm_retractions, &l2_assertions);
             }
         }
+
+        // L1 — reconcile to match: drop retracted keys, then (re)insert
+        // asserted ones.
+        for (norm_title, db_name) in &norm_retractions {
+            self.entries.remove(&CacheKey {
+                normalized_title: norm_title.clone(),
+                db_name: db_name.clone(),
+            });
+        }
+        for (key, cached, epoch) in l1_assertions {
+            self.entries.insert(
+                key,
+                CacheEntry {
+                    result: cached,
+                    inserted_at: Instant::now(),
+                    inserted_epoch: epoch,
+                    last_access: AtomicU64::new(self.tick()),
+                },
+            );
+            self.evict_if_over_capacity();
+        }
     }
 
     /// Remove all entries from both L1 and L2.
@@ -412,6 +1030,46 @@ impl QueryCache {
     pub fn negative_ttl(&self) -> Duration {
         self.negative_ttl
     }
+
+    /// The configured L1 capacity, if any.
+    pub fn max_l1_entries(&self) -> Option<usize> {
+        self.max_l1_entries
+    }
+
+    /// Hit/miss statistics for this session, combined with whatever was
+    /// persisted from prior runs against the same SQLite-backed cache (0 for
+    /// an in-memory-only cache).
+    pub fn stats(&self) -> CacheStats {
+        let session_hits = self.hits();
+        let session_misses = self.misses();
+        CacheStats {
+            session_hits,
+            session_misses,
+            lifetime_hits: self.lifetime_hits_base + session_hits,
+            lifetime_misses: self.lifetime_misses_base + session_misses,
+            last_eviction_epoch: self
+                .sqlite
+                .as_ref()
+                .and_then(|m| m.lock().ok())
+                .and_then(|s| s.last_eviction_epoch()),
+        }
+    }
+}
+
+impl Drop for QueryCache {
+    /// Persist this session's hit/miss counters on top of the lifetime base
+    /// they were loaded from, so the next `open`/`open_with_warmup` resumes
+    /// the running total instead of resetting it to zero.
+    fn drop(&mut self) {
+        if let Some(ref sqlite_mutex) = self.sqlite {
+            if let Ok(store) = sqlite_mutex.lock() {
+                store.save_hit_counters(
+                    self.lifetime_hits_base + self.hits.load(Ordering::Relaxed),
+                    self.lifetime_misses_base + self.misses.load(Ordering::Relaxed),
+                );
+            }
+        }
+    }
 }
 
 fn cached_to_query_result(cached: &CachedResult) -> DbQueryResult {
@@ -551,6 +1209,336 @@ mod tests {
         assert!(cache.get("Paper", "DB").is_none());
     }
 
+    #[test]
+    fn insert_batch_populates_l1_for_every_item() {
+        let cache = QueryCache::default();
+        let items = vec![
+            (
+                "Paper A".to_string(),
+                "CrossRef".to_string(),
+                (Some("Paper A".to_string()), vec![], None),
+            ),
+            (
+                "Paper B".to_string(),
+                "arXiv".to_string(),
+                (None, vec![], None),
+            ),
+        ];
+        cache.insert_batch(&items);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("Paper A", "CrossRef").is_some());
+        assert!(cache.get("Paper B", "arXiv").is_some());
+    }
+
+    #[test]
+    fn insert_batch_persists_all_items_to_l2() {
+        let path = temp_cache_path();
+        let _ = std::fs::remove_file(&path);
+
+        let cache = QueryCache::open(&path, DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL).unwrap();
+        let items = vec![
+            (
+                "Paper A".to_string(),
+                "CrossRef".to_string(),
+                (Some("Paper A".to_string()), vec![], None),
+            ),
+            (
+                "Paper B".to_string(),
+                "arXiv".to_string(),
+                (Some("Paper B".to_string()), vec![], None),
+            ),
+        ];
+        cache.insert_batch(&items);
+        assert_eq!(cache.disk_len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn invalidate_db_only_removes_that_database() {
+        let cache = QueryCache::default();
+        cache.insert("Paper A", "CrossRef", &(Some("Paper A".into()), vec![], None));
+        cache.insert("Paper A", "arXiv", &(Some("Paper A".into()), vec![], None));
+        cache.invalidate_db("CrossRef");
+        assert!(cache.get("Paper A", "CrossRef").is_none());
+        assert!(cache.get("Paper A", "arXiv").is_some());
+    }
+
+    #[test]
+    fn invalidate_where_matches_predicate() {
+        let cache = QueryCache::default();
+        cache.insert("Stale Paper", "CrossRef", &(None, vec![], None));
+        cache.insert("Fresh Paper", "CrossRef", &(Some("Fresh Paper".into()), vec![], None));
+        cache.invalidate_where(|title, _db| title.contains("stale"));
+        assert!(cache.get("Stale Paper", "CrossRef").is_none());
+        assert!(cache.get("Fresh Paper", "CrossRef").is_some());
+    }
+
+    #[test]
+    fn apply_updates_retracts_and_asserts() {
+        let cache = QueryCache::default();
+        cache.insert("Old Paper", "CrossRef", &(None, vec![], None));
+
+        cache.apply_updates(
+            &[("Old Paper".to_string(), "CrossRef".to_string())],
+            &[(
+                "New Paper".to_string(),
+                "CrossRef".to_string(),
+                (Some("New Paper".to_string()), vec![], None),
+            )],
+        );
+
+        assert!(cache.get("Old Paper", "CrossRef").is_none());
+        let cached = cache.get("New Paper", "CrossRef");
+        assert!(cached.is_some());
+        let (title, _, _) = cached.unwrap();
+        assert_eq!(title.unwrap(), "New Paper");
+    }
+
+    #[test]
+    fn apply_updates_persists_to_l2() {
+        let path = temp_cache_path();
+        let _ = std::fs::remove_file(&path);
+
+        let cache = QueryCache::open(&path, DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL).unwrap();
+        cache.insert("Old Paper", "CrossRef", &(None, vec![], None));
+        assert_eq!(cache.disk_len(), 1);
+
+        cache.apply_updates(
+            &[("Old Paper".to_string(), "CrossRef".to_string())],
+            &[(
+                "New Paper".to_string(),
+                "CrossRef".to_string(),
+                (Some("New Paper".to_string()), vec![], None),
+            )],
+        );
+        assert_eq!(cache.disk_len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn stats_track_session_hits_and_misses() {
+        let cache = QueryCache::default();
+        cache.insert("Paper", "CrossRef", &(Some("Paper".into()), vec![], None));
+        cache.get("Paper", "CrossRef");
+        cache.get("Missing", "CrossRef");
+
+        let stats = cache.stats();
+        assert_eq!(stats.session_hits, 1);
+        assert_eq!(stats.session_misses, 1);
+        assert_eq!(stats.lifetime_hits, 1);
+        assert_eq!(stats.lifetime_misses, 1);
+        assert!((stats.session_hit_rate() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn stats_persist_lifetime_counters_across_reopen() {
+        let path = temp_cache_path();
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let cache =
+                QueryCache::open(&path, DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL).unwrap();
+            cache.insert("Paper", "CrossRef", &(Some("Paper".into()), vec![], None));
+            cache.get("Paper", "CrossRef");
+            cache.get("Missing", "CrossRef");
+            // Dropped here — persists lifetime_hits=1, lifetime_misses=1.
+        }
+
+        let cache2 = QueryCache::open(&path, DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL).unwrap();
+        let stats = cache2.stats();
+        assert_eq!(stats.session_hits, 0);
+        assert_eq!(stats.lifetime_hits, 1);
+        assert_eq!(stats.lifetime_misses, 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn open_with_warmup_promotes_recent_rows_into_l1() {
+        let path = temp_cache_path();
+        let _ = std::fs::remove_file(&path);
+
+        {
+            let cache =
+                QueryCache::open(&path, DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL).unwrap();
+            for i in 0..5 {
+                cache.insert(
+                    &format!("Paper {i}"),
+                    "CrossRef",
+                    &(Some(format!("Paper {i}")), vec![], None),
+                );
+            }
+        }
+
+        let warm = QueryCache::open_with_warmup(
+            &path,
+            DEFAULT_POSITIVE_TTL,
+            DEFAULT_NEGATIVE_TTL,
+            3,
+        )
+        .unwrap();
+        assert_eq!(warm.len(), 3);
+        // Warmed entries should be servable without a recorded miss.
+        assert_eq!(warm.misses(), 0);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn l1_uncapped_by_default() {
+        let cache = QueryCache::default();
+        assert_eq!(cache.max_l1_entries(), None);
+    }
+
+    #[tokio::test]
+    async fn get_or_fetch_skips_fetch_on_hit() {
+        let cache = QueryCache::default();
+        cache.insert("Paper", "CrossRef", &(Some("Paper".into()), vec![], None));
+
+        let result: Result<DbQueryResult, String> = cache
+            .get_or_fetch("Paper", "CrossRef", async {
+                panic!("fetch should not run on a cache hit")
+            })
+            .await;
+        assert!(result.unwrap().0.is_some());
+    }
+
+    #[tokio::test]
+    async fn get_or_fetch_caches_successful_fetch() {
+        let cache = QueryCache::default();
+        let result = cache
+            .get_or_fetch("New Paper", "CrossRef", async {
+                Ok::<DbQueryResult, String>((Some("New Paper".into()), vec![], None))
+            })
+            .await
+            .unwrap();
+        assert_eq!(result.0.as_deref(), Some("New Paper"));
+        assert!(cache.get("New Paper", "CrossRef").is_some());
+    }
+
+    #[tokio::test]
+    async fn get_or_fetch_does_not_cache_errors() {
+        let cache = QueryCache::default();
+        let result: Result<DbQueryResult, String> = cache
+            .get_or_fetch("Flaky Paper", "CrossRef", async {
+                Err("timeout".to_string())
+            })
+            .await;
+        assert!(result.is_err());
+        assert!(cache.get("Flaky Paper", "CrossRef").is_none());
+    }
+
+    #[tokio::test]
+    async fn get_or_fetch_coalesces_concurrent_misses() {
+        let cache = Arc::new(QueryCache::default());
+        let fetch_count = Arc::new(AtomicU64::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let cache = Arc::clone(&cache);
+            let fetch_count = Arc::clone(&fetch_count);
+            handles.push(tokio::spawn(async move {
+                cache
+                    .get_or_fetch("Popular Paper", "CrossRef", async {
+                        fetch_count.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        Ok::<DbQueryResult, String>((
+                            Some("Popular Paper".into()),
+                            vec![],
+                            None,
+                        ))
+                    })
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            let result: Result<DbQueryResult, String> = handle.await.unwrap();
+            assert_eq!(result.unwrap().0.as_deref(), Some("Popular Paper"));
+        }
+        assert_eq!(fetch_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn get_or_fetch_panic_during_fetch_does_not_wedge_later_callers() {
+        let cache = Arc::new(QueryCache::default());
+
+        let leader = {
+            let cache = Arc::clone(&cache);
+            tokio::spawn(async move {
+                cache
+                    .get_or_fetch("Flaky Paper", "CrossRef", async {
+                        panic!("simulated fetch panic");
+                        #[allow(unreachable_code)]
+                        Ok::<DbQueryResult, String>((None, vec![], None))
+                    })
+                    .await
+            })
+        };
+        assert!(leader.await.is_err());
+
+        // A panicking leader must not leave the `in_flight` entry orphaned:
+        // the next caller should become a fresh leader and succeed normally
+        // instead of hanging forever as a waiter on a leader that's gone.
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            cache.get_or_fetch("Flaky Paper", "CrossRef", async {
+                Ok::<DbQueryResult, String>((Some("Flaky Paper".into()), vec![], None))
+            }),
+        )
+        .await
+        .expect("get_or_fetch should not hang after a panicking leader")
+        .unwrap();
+        assert_eq!(result.0.as_deref(), Some("Flaky Paper"));
+    }
+
+    #[test]
+    fn l1_eviction_keeps_size_at_or_below_capacity() {
+        let cache = QueryCache::with_l1_capacity(
+            DEFAULT_POSITIVE_TTL,
+            DEFAULT_NEGATIVE_TTL,
+            Some(10),
+        );
+        for i in 0..100 {
+            cache.insert(
+                &format!("Paper {i}"),
+                "CrossRef",
+                &(Some(format!("Paper {i}")), vec![], None),
+            );
+        }
+        assert!(
+            cache.len() <= 11,
+            "expected L1 to stay near capacity, got {}",
+            cache.len()
+        );
+    }
+
+    #[test]
+    fn l1_eviction_prefers_recently_touched_entries() {
+        // Capacity exactly matches the initial entry count, and the sample
+        // size (8) covers all of them, so the single overflow insert below
+        // deterministically considers every entry rather than a subsample.
+        let cache =
+            QueryCache::with_l1_capacity(DEFAULT_POSITIVE_TTL, DEFAULT_NEGATIVE_TTL, Some(5));
+        for i in 0..5 {
+            cache.insert(
+                &format!("Paper {i}"),
+                "CrossRef",
+                &(Some(format!("Paper {i}")), vec![], None),
+            );
+        }
+        // Touch "Paper 0" so its last-access tick is newer than "Paper 1"..4.
+        assert!(cache.get("Paper 0", "CrossRef").is_some());
+
+        // One more insert pushes L1 one over capacity, evicting the entry
+        // with the oldest tick among the (fully sampled) 6 entries.
+        cache.insert("Paper 5", "CrossRef", &(Some("Paper 5".into()), vec![], None));
+
+        assert!(cache.get("Paper 0", "CrossRef").is_some());
+    }
+
     // ── SQLite persistence tests ──────────────────────────────────────
 
     use std::sync::atomic::AtomicU32;
@@ -30,6 +30,25 @@ pub struct DblpOnline;
 /// Offline DBLP backend backed by a local SQLite database with FTS5.
 pub struct DblpOffline {
     pub db: Arc<Mutex<hallucinator_dblp::DblpDatabase>>,
+    /// Maximum Hamming distance between 64-bit SimHash fingerprints
+    /// (`hallucinator_dblp::db::search_titles_by_simhash`) still accepted as
+    /// a near-duplicate match when the primary lookup finds nothing — the
+    /// last-resort path for titles too OCR-mangled to share enough exact
+    /// tokens. Defaults to
+    /// `hallucinator_dblp::db::DEFAULT_SIMHASH_MAX_DISTANCE`.
+    pub simhash_max_distance: u32,
+}
+
+impl DblpOffline {
+    /// Wrap an already-open offline DBLP database, using the default
+    /// SimHash near-duplicate threshold. Set `simhash_max_distance`
+    /// directly on the result to override it.
+    pub fn new(db: Arc<Mutex<hallucinator_dblp::DblpDatabase>>) -> Self {
+        Self {
+            db,
+            simhash_max_distance: hallucinator_dblp::db::DEFAULT_SIMHASH_MAX_DISTANCE,
+        }
+    }
 }
 
 impl DatabaseBackend for DblpOffline {
@@ -49,18 +68,28 @@ impl DatabaseBackend for DblpOffline {
     ) -> Pin<Box<dyn Future<Output = Result<DbQueryResult, DbQueryError>> + Send + 'a>> {
         let db = Arc::clone(&self.db);
         let title = title.to_string();
+        let simhash_max_distance = self.simhash_max_distance;
         Box::pin(async move {
-            let result = tokio::task::spawn_blocking(move || {
+            let found = tokio::task::spawn_blocking(move || {
                 let db = db.lock().map_err(|e| DbQueryError::Other(e.to_string()))?;
-                db.query(&title)
-                    .map_err(|e| DbQueryError::Other(e.to_string()))
+                // Exact/typo-tolerant recall first; only fall through to the
+                // (pricier) SimHash near-duplicate path if it finds nothing
+                // usable, so the common case pays no extra cost.
+                match db.query(&title) {
+                    Ok(Some(qr)) if !qr.record.authors.is_empty() => Ok(Some(qr)),
+                    Ok(_) => db
+                        .query_near_duplicate(&title, simhash_max_distance)
+                        .map(|near| near.filter(|qr| !qr.record.authors.is_empty())),
+                    Err(e) => Err(e),
+                }
+                .map_err(|e: hallucinator_dblp::DblpError| DbQueryError::Other(e.to_string()))
             })
             .await
             .map_err(|e| DbQueryError::Other(e.to_string()))??;
 
-            match result {
-                Some(qr) if !qr.record.authors.is_empty() => Ok(DbQueryResult::found(
-                    qr.record.title,
+            match found {
+                Some(qr) => Ok((
+                    Some(qr.record.title),
                     qr.record
                         .authors
                         .into_iter()
@@ -69,7 +98,7 @@ impl DatabaseBackend for DblpOffline {
                     qr.record.url,
                 )),
                 // Skip results with empty authors - let other DBs verify
-                _ => Ok(DbQueryResult::not_found()),
+                None => Ok((None, vec![], None)),
             }
         })
     }
@@ -151,11 +180,11 @@ impl DatabaseBackend for DblpOnline {
                         authors.into_iter().map(|a| strip_dblp_suffix(&a)).collect();
                     let paper_url = info["url"].as_str().map(String::from);
 
-                    return Ok(DbQueryResult::found(found_title, authors, paper_url));
+                    return Ok((Some(found_title.to_string()), authors, paper_url));
                 }
             }
 
-            Ok(DbQueryResult::not_found())
+            Ok((None, vec![], None))
         })
     }
 }
@@ -0,0 +1,116 @@
+//! Database backend abstraction.
+//!
+//! [`DatabaseBackend`] is the trait every external (CrossRef, Semantic
+//! Scholar, DBLP, ...) or local-offline lookup implements. [`DbQueryResult`]
+//! is the `(found_title, authors, paper_url)` shape callers get back on
+//! success; [`DbQueryError`] is the structured failure taxonomy threaded
+//! through [`crate::rate_limit`] and [`crate::cache`] so callers can tell a
+//! rate limit apart from a timeout apart from "genuinely not found".
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+pub mod dblp;
+
+/// `(found_title, authors, paper_url)`. `found_title` is `None` when the
+/// database has no matching record for the query.
+pub type DbQueryResult = (Option<String>, Vec<String>, Option<String>);
+
+/// A queryable external (or local-offline) bibliographic database.
+pub trait DatabaseBackend: Send + Sync {
+    /// Human-readable name, used as the cache/rate-limit/circuit-breaker key.
+    fn name(&self) -> &str;
+
+    /// Whether this backend runs entirely locally (no network I/O, so
+    /// rate limiting and circuit breaking don't apply to it).
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    /// Look up `title`, returning `Ok(DbQueryResult)` on a definitive
+    /// found/not-found answer, or `Err(DbQueryError)` if the lookup itself
+    /// failed (network error, rate limit, timeout, ...).
+    fn query<'a>(
+        &'a self,
+        title: &'a str,
+        client: &'a reqwest::Client,
+        timeout: Duration,
+    ) -> Pin<Box<dyn Future<Output = Result<DbQueryResult, DbQueryError>> + Send + 'a>>;
+}
+
+/// Why a [`DatabaseBackend::query`] call failed to produce a definitive
+/// found/not-found answer.
+///
+/// Each variant carries a stable [`DbQueryError::code`] string so callers
+/// (retry logic, circuit breakers, JSON exports) can branch on *why* a query
+/// failed without parsing error messages.
+#[derive(Debug, Clone)]
+pub enum DbQueryError {
+    /// The server responded 429, optionally telling us how long to wait via
+    /// `Retry-After`.
+    RateLimited { retry_after: Option<Duration> },
+    /// The request didn't complete within the caller's timeout.
+    Timeout,
+    /// A non-2xx, non-429 HTTP response.
+    Http(u16),
+    /// The response body couldn't be parsed as expected.
+    Parse(String),
+    /// Connection-level failure: refused, reset, aborted, DNS, etc.
+    Network(String),
+    /// The database's circuit breaker is open (too many recent failures);
+    /// the query was skipped without being attempted.
+    Unavailable,
+    /// Catch-all for errors that don't fit a more specific variant above.
+    Other(String),
+}
+
+impl DbQueryError {
+    /// Stable, machine-readable code for this error, suitable for JSON/CSV
+    /// exports and for downstream tooling that wants to group failures
+    /// without re-deriving the classification logic in this module.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DbQueryError::RateLimited { .. } => "rate_limited",
+            DbQueryError::Timeout => "timeout",
+            DbQueryError::Http(_) => "http_error",
+            DbQueryError::Parse(_) => "parse_error",
+            DbQueryError::Network(_) => "network_error",
+            DbQueryError::Unavailable => "unavailable",
+            DbQueryError::Other(_) => "other",
+        }
+    }
+
+    /// Whether retrying this exact query again, after an appropriate delay,
+    /// has a reasonable chance of succeeding — rate limits, timeouts, and
+    /// connection-level hiccups are transient; a parse error or a 4xx other
+    /// than 429 generally is not.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DbQueryError::RateLimited { .. } | DbQueryError::Timeout | DbQueryError::Network(_) => {
+                true
+            }
+            DbQueryError::Http(status) => *status >= 500,
+            DbQueryError::Parse(_) | DbQueryError::Other(_) | DbQueryError::Unavailable => false,
+        }
+    }
+}
+
+impl std::fmt::Display for DbQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbQueryError::RateLimited { retry_after: Some(d) } => {
+                write!(f, "rate limited, retry after {d:?}")
+            }
+            DbQueryError::RateLimited { retry_after: None } => write!(f, "rate limited"),
+            DbQueryError::Timeout => write!(f, "request timed out"),
+            DbQueryError::Http(status) => write!(f, "HTTP {status}"),
+            DbQueryError::Parse(msg) => write!(f, "parse error: {msg}"),
+            DbQueryError::Network(msg) => write!(f, "network error: {msg}"),
+            DbQueryError::Unavailable => write!(f, "circuit breaker open, database unavailable"),
+            DbQueryError::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for DbQueryError {}
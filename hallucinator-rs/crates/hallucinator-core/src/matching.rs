@@ -0,0 +1,844 @@
+//! Title similarity matching.
+//!
+//! [`normalize_title`] folds away cosmetic differences (case, diacritics, a
+//! handful of HTML entities, Greek letters spelled out vs. transliterated)
+//! so that two renderings of the same title compare equal. [`TitleMatcher`]
+//! layers three cheap-to-expensive ranking rules on top of that normalized
+//! form — typo tolerance, token proximity, and verbatim exactness — rather
+//! than collapsing everything into one fuzzy ratio, so a reordered-words
+//! title and a single-OCR-typo title don't get judged by the same yardstick.
+//! [`titles_match`] and [`best_match_score`] are thin wrappers over
+//! [`TitleMatcher::default`] for callers that don't need to tune it.
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Confidence (0–100, same scale as [`TitleMatcher::score`]) above which
+/// [`titles_match`] considers two titles the same paper.
+pub const DEFAULT_MATCH_THRESHOLD: f64 = 85.0;
+
+/// Named HTML entities that show up often enough in scraped titles to be
+/// worth decoding by hand rather than pulling in a whole HTML parser.
+const HTML_ENTITIES: &[(&str, &str)] = &[
+    ("&amp;", "&"),
+    ("&lt;", "<"),
+    ("&gt;", ">"),
+    ("&quot;", "\""),
+    ("&apos;", "'"),
+    ("&nbsp;", " "),
+];
+
+/// Lowercase Greek letters spelled out, so "α-divergence" and
+/// "alpha-divergence" normalize to the same tokens. Uppercase forms are
+/// covered by lowercasing the input before transliteration.
+const GREEK_LETTERS: &[(char, &str)] = &[
+    ('α', "alpha"),
+    ('β', "beta"),
+    ('γ', "gamma"),
+    ('δ', "delta"),
+    ('ε', "epsilon"),
+    ('ζ', "zeta"),
+    ('η', "eta"),
+    ('θ', "theta"),
+    ('ι', "iota"),
+    ('κ', "kappa"),
+    ('λ', "lambda"),
+    ('μ', "mu"),
+    ('ν', "nu"),
+    ('ξ', "xi"),
+    ('ο', "omicron"),
+    ('π', "pi"),
+    ('ρ', "rho"),
+    ('σ', "sigma"),
+    ('τ', "tau"),
+    ('υ', "upsilon"),
+    ('φ', "phi"),
+    ('χ', "chi"),
+    ('ψ', "psi"),
+    ('ω', "omega"),
+];
+
+/// Decode the small set of [`HTML_ENTITIES`] plus numeric character
+/// references (`&#65;`, `&#x41;`).
+fn decode_html_entities(title: &str) -> String {
+    let mut decoded = title.to_string();
+    for (entity, replacement) in HTML_ENTITIES {
+        decoded = decoded.replace(entity, replacement);
+    }
+
+    let mut out = String::with_capacity(decoded.len());
+    let bytes = decoded.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if decoded[i..].starts_with("&#") {
+            if let Some(end) = decoded[i..].find(';') {
+                let body = &decoded[i + 2..i + end];
+                let code_point = if let Some(hex) = body.strip_prefix('x').or(body.strip_prefix('X')) {
+                    u32::from_str_radix(hex, 16).ok()
+                } else {
+                    body.parse::<u32>().ok()
+                };
+                if let Some(c) = code_point.and_then(char::from_u32) {
+                    out.push(c);
+                    i += end + 1;
+                    continue;
+                }
+            }
+        }
+        let ch = decoded[i..].chars().next().unwrap();
+        out.push(ch);
+        i += ch.len_utf8();
+    }
+    out
+}
+
+/// Replace Greek letters (upper- or lowercase) with their spelled-out names.
+fn transliterate_greek(title: &str) -> String {
+    let mut out = String::with_capacity(title.len());
+    for c in title.chars() {
+        let folded = c.to_lowercase().next().unwrap_or(c);
+        match GREEK_LETTERS.iter().find(|(letter, _)| *letter == folded) {
+            Some((_, name)) => out.push_str(name),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Normalize a title for matching and cache-key purposes: decode HTML
+/// entities, spell out Greek letters, strip diacritics, drop punctuation,
+/// collapse whitespace, and lowercase.
+///
+/// Two titles that normalize to the same string are considered identical by
+/// every matching rule in this module.
+pub fn normalize_title(title: &str) -> String {
+    let decoded = decode_html_entities(title);
+    let transliterated = transliterate_greek(&decoded);
+    let stripped: String = transliterated
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect();
+
+    let cleaned: String = stripped
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Whitespace-split tokens of an already-[`normalize_title`]d string.
+fn tokenize(normalized: &str) -> Vec<&str> {
+    normalized.split_whitespace().collect()
+}
+
+/// Classic Levenshtein edit distance, used to check whether two tokens are
+/// a plausible typo of each other. Tokens are short, so the O(n*m) table is
+/// negligible.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut curr = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        curr[0] = i;
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m]
+}
+
+/// Edit distance to tolerate between two tokens before they stop counting as
+/// the same (typo'd) word, scaled by the shorter token's length.
+fn token_edit_budget(token: &str) -> usize {
+    match token.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// One criterion [`TitleMatcher`] ranks a title pair by, evaluated in the
+/// order rules are registered — earlier rules are cheaper and decide most
+/// pairs outright, so later (pricier) rules only run on genuinely close
+/// calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchRule {
+    /// Fraction of tokens (of the shorter title) that have a match in the
+    /// other title within [`token_edit_budget`] edits. Catches the common
+    /// case of one or two OCR/typo'd words among otherwise identical
+    /// titles.
+    Typo,
+    /// Among tokens [`MatchRule::Typo`] matched, penalizes ones that
+    /// appear far apart or reordered between the two titles. Cheap to skip
+    /// entirely when the typo rule already found nothing.
+    Proximity,
+    /// Longest verbatim substring shared between the two normalized
+    /// titles, as a fraction of the shorter title's length. The priciest
+    /// rule (an O(n*m) scan), so it only runs once the cheaper rules agree
+    /// the titles are plausibly the same paper.
+    Exactness,
+}
+
+/// Per-rule scores (each 0.0–100.0) for one title pair, in [`MatchRule`]
+/// order. Two candidates are compared by this tuple lexicographically, so a
+/// decisive difference on an earlier (cheaper) rule settles the comparison
+/// without needing the later rules' scores.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuleScores {
+    pub typo: f64,
+    pub proximity: f64,
+    pub exactness: f64,
+}
+
+impl RuleScores {
+    const ZERO: RuleScores = RuleScores {
+        typo: 0.0,
+        proximity: 0.0,
+        exactness: 0.0,
+    };
+
+    /// Blend the three rule scores into a single 0–100 confidence, weighted
+    /// toward the typo rule since it's the strongest signal that two titles
+    /// describe the same paper.
+    pub fn combined(&self) -> f64 {
+        self.typo * 0.6 + self.proximity * 0.15 + self.exactness * 0.25
+    }
+
+    fn cmp_tuple(&self, other: &RuleScores) -> std::cmp::Ordering {
+        self.typo
+            .partial_cmp(&other.typo)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| {
+                self.proximity
+                    .partial_cmp(&other.proximity)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .then_with(|| {
+                self.exactness
+                    .partial_cmp(&other.exactness)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
+
+/// Below this [`MatchRule::Typo`] score, the titles share too few tokens to
+/// plausibly be the same paper — [`TitleMatcher::score_pair`] skips the
+/// proximity and exactness rules entirely rather than paying for them.
+const TYPO_SHORT_CIRCUIT_THRESHOLD: f64 = 1.0;
+
+/// Ordered chain of ranking rules for comparing two titles. Construct with
+/// [`TitleMatcher::builder`] to customize which rules run and the match
+/// threshold; [`TitleMatcher::default`] runs all three in the order
+/// documented on [`MatchRule`].
+#[derive(Debug, Clone)]
+pub struct TitleMatcher {
+    rules: Vec<MatchRule>,
+    threshold: f64,
+}
+
+impl Default for TitleMatcher {
+    fn default() -> Self {
+        TitleMatcherBuilder::new().build()
+    }
+}
+
+impl TitleMatcher {
+    /// Start building a [`TitleMatcher`] with a custom rule chain and/or
+    /// threshold.
+    pub fn builder() -> TitleMatcherBuilder {
+        TitleMatcherBuilder::new()
+    }
+
+    /// Score `a` against `b`, running only the rules configured on this
+    /// matcher, in order, short-circuiting the remainder once
+    /// [`MatchRule::Typo`] indicates there's no point continuing.
+    pub fn score_pair(&self, a: &str, b: &str) -> RuleScores {
+        self.score_normalized(&normalize_title(a), &normalize_title(b))
+    }
+
+    /// Like [`score_pair`](Self::score_pair), but takes titles that are
+    /// already [`normalize_title`]d — used by [`TitleIndex`], which
+    /// precomputes every candidate's normalized form once at build time
+    /// instead of paying for it again on every query.
+    fn score_normalized(&self, norm_a: &str, norm_b: &str) -> RuleScores {
+        if norm_a.is_empty() || norm_b.is_empty() {
+            return RuleScores::ZERO;
+        }
+        if norm_a == norm_b {
+            return RuleScores {
+                typo: 100.0,
+                proximity: 100.0,
+                exactness: 100.0,
+            };
+        }
+
+        let tokens_a = tokenize(norm_a);
+        let tokens_b = tokenize(norm_b);
+
+        let mut scores = RuleScores::ZERO;
+        for rule in &self.rules {
+            match rule {
+                MatchRule::Typo => {
+                    scores.typo = typo_score(&tokens_a, &tokens_b);
+                    if scores.typo < TYPO_SHORT_CIRCUIT_THRESHOLD {
+                        return scores;
+                    }
+                }
+                MatchRule::Proximity => {
+                    scores.proximity = proximity_score(&tokens_a, &tokens_b);
+                }
+                MatchRule::Exactness => {
+                    scores.exactness = exactness_score(norm_a, norm_b);
+                }
+            }
+        }
+        scores
+    }
+
+    /// Blended 0–100 confidence that `a` and `b` are the same title. A thin
+    /// wrapper over [`score_pair`](Self::score_pair)`.combined()`.
+    pub fn score(&self, a: &str, b: &str) -> f64 {
+        self.score_pair(a, b).combined()
+    }
+
+    /// Whether `a` and `b` score at or above this matcher's threshold.
+    pub fn matches(&self, a: &str, b: &str) -> bool {
+        self.score(a, b) >= self.threshold
+    }
+
+    /// Index and combined score of whichever `candidates` entry best
+    /// matches `query`, or `(None, 0.0)` if `candidates` is empty or
+    /// `query` normalizes to nothing.
+    ///
+    /// Candidates are ranked by [`RuleScores::cmp_tuple`] (the same
+    /// left-to-right rule comparison [`MatchRule`] documents), not just the
+    /// blended score, so a tie on the blended number still breaks toward
+    /// whichever candidate wins on the earlier, cheaper rules.
+    pub fn best_match<S: AsRef<str>>(&self, query: &str, candidates: &[S]) -> (Option<usize>, f64) {
+        if normalize_title(query).is_empty() {
+            return (None, 0.0);
+        }
+
+        let mut best_idx = None;
+        let mut best_scores = RuleScores::ZERO;
+        for (i, candidate) in candidates.iter().enumerate() {
+            let scores = self.score_pair(query, candidate.as_ref());
+            if best_idx.is_none() || scores.cmp_tuple(&best_scores) == std::cmp::Ordering::Greater {
+                best_idx = Some(i);
+                best_scores = scores;
+            }
+        }
+        (best_idx, best_scores.combined())
+    }
+}
+
+/// Builder for [`TitleMatcher`].
+#[derive(Debug, Clone)]
+pub struct TitleMatcherBuilder {
+    rules: Vec<MatchRule>,
+    threshold: f64,
+}
+
+impl TitleMatcherBuilder {
+    fn new() -> Self {
+        Self {
+            rules: vec![MatchRule::Typo, MatchRule::Proximity, MatchRule::Exactness],
+            threshold: DEFAULT_MATCH_THRESHOLD,
+        }
+    }
+
+    /// Replace the default rule chain (typo, proximity, exactness) with a
+    /// custom ordering or subset. Rules run in the order given.
+    pub fn rules(mut self, rules: Vec<MatchRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Set the score (0–100) [`TitleMatcher::matches`] requires.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn build(self) -> TitleMatcher {
+        TitleMatcher {
+            rules: self.rules,
+            threshold: self.threshold,
+        }
+    }
+}
+
+/// Default minimum number of normalized content tokens a candidate must
+/// share with the query before [`TitleIndex::best_match`] bothers scoring it
+/// with the full [`TitleMatcher`] rule chain.
+const DEFAULT_MIN_SHARED_TOKENS: usize = 1;
+
+/// Inverted-index candidate retrieval over a fixed corpus of ground-truth
+/// titles, so matching a query title against thousands of candidates no
+/// longer means running [`TitleMatcher`] against every single one.
+///
+/// Each corpus title's [`normalize_title`]d form and token list are computed
+/// once, at [`TitleIndex::build`] time, and reused across every query. A
+/// query's tokens are looked up against an inverted `token -> title ids` map
+/// to gather the (usually small) set of titles sharing at least
+/// [`TitleIndex::min_shared_tokens`] tokens, and only that set is scored —
+/// retrieval becomes roughly linear in the number of shared-token
+/// candidates rather than the corpus size. Falls back to scoring the whole
+/// corpus whenever no candidate shares enough tokens, *or* the restricted
+/// set's best score doesn't clear `matcher`'s accept threshold — an
+/// irrelevant candidate sharing just one filler token can otherwise keep
+/// `candidate_ids` non-empty and silently hide a heavily-typo'd true match
+/// that shares no tokens at all. So every *accepted* match (scoring at or
+/// above the threshold) is always identical to a full linear scan with the
+/// same [`TitleMatcher`]; only a below-threshold "nothing really matched"
+/// result can differ from one, and callers treat those the same either way.
+pub struct TitleIndex {
+    titles: Vec<String>,
+    normalized: Vec<String>,
+    token_index: std::collections::HashMap<String, Vec<usize>>,
+    min_shared_tokens: usize,
+}
+
+impl TitleIndex {
+    /// Build an index over `titles`, normalizing and tokenizing each one
+    /// exactly once.
+    pub fn build(titles: &[String]) -> Self {
+        let normalized: Vec<String> = titles.iter().map(|t| normalize_title(t)).collect();
+
+        let mut token_index: std::collections::HashMap<String, Vec<usize>> =
+            std::collections::HashMap::new();
+        for (id, norm) in normalized.iter().enumerate() {
+            for token in tokenize(norm) {
+                token_index.entry(token.to_string()).or_default().push(id);
+            }
+        }
+
+        Self {
+            titles: titles.to_vec(),
+            normalized,
+            token_index,
+            min_shared_tokens: DEFAULT_MIN_SHARED_TOKENS,
+        }
+    }
+
+    /// Require candidates to share at least `min_shared_tokens` tokens with
+    /// the query before they're scored, rather than the default of 1. A
+    /// higher floor shrinks the candidate set further on large corpora, at
+    /// the risk of missing a genuine match whose only shared tokens are a
+    /// small minority of the title.
+    pub fn with_min_shared_tokens(mut self, min_shared_tokens: usize) -> Self {
+        self.min_shared_tokens = min_shared_tokens.max(1);
+        self
+    }
+
+    /// Number of titles in the corpus.
+    pub fn len(&self) -> usize {
+        self.titles.len()
+    }
+
+    /// Whether the corpus is empty.
+    pub fn is_empty(&self) -> bool {
+        self.titles.is_empty()
+    }
+
+    /// Title ids sharing at least one token with `query_tokens`, along with
+    /// how many tokens they share, gathered via a set union over each
+    /// token's posting list rather than scanning every corpus title.
+    fn candidate_counts(&self, query_tokens: &[&str]) -> std::collections::HashMap<usize, usize> {
+        let mut counts = std::collections::HashMap::new();
+        for token in query_tokens {
+            if let Some(ids) = self.token_index.get(*token) {
+                for &id in ids {
+                    *counts.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Index and combined score of whichever corpus title best matches
+    /// `query`, using [`TitleMatcher::default`]. See
+    /// [`best_match_with`](Self::best_match_with) to use a custom matcher.
+    pub fn best_match(&self, query: &str) -> (Option<usize>, f64) {
+        self.best_match_with(&TitleMatcher::default(), query)
+    }
+
+    /// Like [`best_match`](Self::best_match), scoring with `matcher` instead
+    /// of the default rule chain.
+    pub fn best_match_with(&self, matcher: &TitleMatcher, query: &str) -> (Option<usize>, f64) {
+        let norm_query = normalize_title(query);
+        if norm_query.is_empty() {
+            return (None, 0.0);
+        }
+        let query_tokens = tokenize(&norm_query);
+
+        let counts = self.candidate_counts(&query_tokens);
+        let candidate_ids: Vec<usize> = counts
+            .iter()
+            .filter(|(_, &shared)| shared >= self.min_shared_tokens)
+            .map(|(&id, _)| id)
+            .collect();
+
+        let (best_idx, best_scores) =
+            Self::scan(matcher, &norm_query, &self.normalized, candidate_ids.iter().copied());
+
+        // The restricted candidate set can miss the true best match even
+        // when non-empty: an irrelevant title sharing just one filler token
+        // stays in `candidate_ids` and can mask a heavily-typo'd true match
+        // that shares no tokens with the query at all. Whenever the
+        // restricted best doesn't clear the matcher's accept threshold —
+        // including when there were no candidates — fall back to scoring
+        // the whole corpus instead of returning a result a full linear scan
+        // might beat.
+        if best_idx.is_none() || best_scores.combined() < matcher.threshold {
+            let (full_idx, full_scores) =
+                Self::scan(matcher, &norm_query, &self.normalized, 0..self.titles.len());
+            if full_idx.is_some()
+                && (best_idx.is_none() || full_scores.cmp_tuple(&best_scores) == std::cmp::Ordering::Greater)
+            {
+                return (full_idx, full_scores.combined());
+            }
+        }
+
+        (best_idx, best_scores.combined())
+    }
+
+    /// Score every title in `ids` against `norm_query`, returning the
+    /// index (into `normalized`, i.e. a title id) and scores of the best
+    /// one, or `(None, RuleScores::ZERO)` if `ids` is empty.
+    fn scan(
+        matcher: &TitleMatcher,
+        norm_query: &str,
+        normalized: &[String],
+        ids: impl Iterator<Item = usize>,
+    ) -> (Option<usize>, RuleScores) {
+        let mut best_idx = None;
+        let mut best_scores = RuleScores::ZERO;
+        for id in ids {
+            let scores = matcher.score_normalized(norm_query, &normalized[id]);
+            if best_idx.is_none() || scores.cmp_tuple(&best_scores) == std::cmp::Ordering::Greater {
+                best_idx = Some(id);
+                best_scores = scores;
+            }
+        }
+        (best_idx, best_scores)
+    }
+
+    /// The original (non-normalized) title at `id`, as passed to
+    /// [`build`](Self::build).
+    pub fn title(&self, id: usize) -> Option<&str> {
+        self.titles.get(id).map(String::as_str)
+    }
+}
+
+/// Fraction (as 0–100) of the shorter token list that has a match in the
+/// other list within [`token_edit_budget`] edits. Each token can only be
+/// consumed by one match, so repeated words can't inflate the score.
+fn typo_score(tokens_a: &[&str], tokens_b: &[&str]) -> f64 {
+    if tokens_a.is_empty() || tokens_b.is_empty() {
+        return 0.0;
+    }
+
+    let (shorter, longer) = if tokens_a.len() <= tokens_b.len() {
+        (tokens_a, tokens_b)
+    } else {
+        (tokens_b, tokens_a)
+    };
+
+    let mut used = vec![false; longer.len()];
+    let mut matched = 0usize;
+    for token in shorter {
+        let budget = token_edit_budget(token);
+        if let Some(j) = longer.iter().enumerate().position(|(j, other)| {
+            !used[j] && (*other == *token || edit_distance(token, other) <= budget)
+        }) {
+            used[j] = true;
+            matched += 1;
+        }
+    }
+
+    matched as f64 / shorter.len() as f64 * 100.0
+}
+
+/// Rewards matched tokens that sit at (nearly) the same relative position in
+/// both titles; penalizes reordering and large gaps. `0.0` if nothing
+/// lines up positionally.
+fn proximity_score(tokens_a: &[&str], tokens_b: &[&str]) -> f64 {
+    if tokens_a.is_empty() || tokens_b.is_empty() {
+        return 0.0;
+    }
+
+    let mut total_penalty = 0.0;
+    let mut considered = 0usize;
+    let mut used = vec![false; tokens_b.len()];
+
+    for (i, token) in tokens_a.iter().enumerate() {
+        let rel_a = i as f64 / tokens_a.len() as f64;
+        if let Some(j) = tokens_b.iter().enumerate().position(|(j, other)| !used[j] && other == token) {
+            used[j] = true;
+            let rel_b = j as f64 / tokens_b.len() as f64;
+            total_penalty += (rel_a - rel_b).abs();
+            considered += 1;
+        }
+    }
+
+    if considered == 0 {
+        return 0.0;
+    }
+
+    let avg_penalty = total_penalty / considered as f64;
+    ((1.0 - avg_penalty).max(0.0)) * 100.0
+}
+
+/// Longest verbatim substring shared by `a` and `b`, as a fraction of the
+/// shorter string's length, scaled to 0–100.
+fn exactness_score(a: &str, b: &str) -> f64 {
+    let (shorter, longer) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if shorter.is_empty() {
+        return 0.0;
+    }
+
+    let shorter_bytes = shorter.as_bytes();
+    let mut best_len = 0usize;
+    for start in 0..shorter_bytes.len() {
+        for end in (start + 1..=shorter_bytes.len()).rev() {
+            let len = end - start;
+            if len <= best_len {
+                break;
+            }
+            if longer.contains(&shorter[start..end]) {
+                best_len = len;
+                break;
+            }
+        }
+    }
+
+    best_len as f64 / shorter.len() as f64 * 100.0
+}
+
+/// Whether `a` and `b` are the same title, using [`TitleMatcher::default`]'s
+/// rule chain and [`DEFAULT_MATCH_THRESHOLD`].
+pub fn titles_match(a: &str, b: &str) -> bool {
+    TitleMatcher::default().matches(a, b)
+}
+
+/// Index and combined score of whichever `candidates` entry best matches
+/// `query`, using [`TitleMatcher::default`]. `(None, 0.0)` if `candidates`
+/// is empty or `query` normalizes to nothing.
+pub fn best_match_score(query: &str, candidates: &[String]) -> (Option<usize>, f64) {
+    TitleMatcher::default().best_match(query, candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_diacritics_and_case() {
+        assert_eq!(normalize_title("Résumé of Methods"), "resume of methods");
+    }
+
+    #[test]
+    fn normalize_decodes_html_entities() {
+        assert_eq!(
+            normalize_title("Attention &amp; Memory"),
+            "attention memory"
+        );
+    }
+
+    #[test]
+    fn normalize_decodes_numeric_entities() {
+        assert_eq!(normalize_title("Na&#239;ve Bayes"), "naive bayes");
+    }
+
+    #[test]
+    fn normalize_transliterates_greek_letters() {
+        assert_eq!(
+            normalize_title("α-Divergence Minimization"),
+            "alpha divergence minimization"
+        );
+    }
+
+    #[test]
+    fn normalize_collapses_punctuation_and_whitespace() {
+        assert_eq!(
+            normalize_title("  Deep   Learning: A Survey!  "),
+            "deep learning a survey"
+        );
+    }
+
+    #[test]
+    fn titles_match_identical_after_normalization() {
+        assert!(titles_match(
+            "Attention Is All You Need",
+            "attention is all you need"
+        ));
+    }
+
+    #[test]
+    fn titles_match_tolerates_single_typo() {
+        assert!(titles_match(
+            "Attention Is All You Need",
+            "Attentoin Is All You Need"
+        ));
+    }
+
+    #[test]
+    fn titles_match_rejects_unrelated_titles() {
+        assert!(!titles_match(
+            "Attention Is All You Need",
+            "Deep Residual Learning for Image Recognition"
+        ));
+    }
+
+    #[test]
+    fn typo_score_ignores_word_order() {
+        let a = normalize_title("Neural Machine Translation");
+        let b = normalize_title("Translation Neural Machine");
+        let tokens_a = tokenize(&a);
+        let tokens_b = tokenize(&b);
+        assert_eq!(typo_score(&tokens_a, &tokens_b), 100.0);
+    }
+
+    #[test]
+    fn proximity_score_prefers_in_order_titles() {
+        let a = normalize_title("Neural Machine Translation Survey");
+        let same_order = normalize_title("Neural Machine Translation Overview");
+        let reordered = normalize_title("Translation Survey Neural Machine");
+
+        let tokens_a = tokenize(&a);
+        let in_order_score = proximity_score(&tokens_a, &tokenize(&same_order));
+        let reordered_score = proximity_score(&tokens_a, &tokenize(&reordered));
+        assert!(in_order_score > reordered_score);
+    }
+
+    #[test]
+    fn exactness_score_rewards_verbatim_overlap() {
+        let score = exactness_score("attention is all you need", "is all you need attention");
+        assert!(score > 50.0, "expected high overlap score, got {score}");
+    }
+
+    #[test]
+    fn best_match_score_picks_closest_candidate() {
+        let candidates = vec![
+            "Deep Residual Learning for Image Recognition".to_string(),
+            "Attention Is All You Need".to_string(),
+            "Generative Adversarial Networks".to_string(),
+        ];
+        let (idx, score) = best_match_score("Attention is all you need", &candidates);
+        assert_eq!(idx, Some(1));
+        assert!(score > DEFAULT_MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn best_match_score_empty_candidates() {
+        assert_eq!(best_match_score("Anything", &[]), (None, 0.0));
+    }
+
+    #[test]
+    fn builder_custom_threshold_rejects_near_matches() {
+        let strict = TitleMatcher::builder().threshold(99.9).build();
+        assert!(!strict.matches(
+            "Attention Is All You Need",
+            "Attentoin Is All You Need"
+        ));
+    }
+
+    #[test]
+    fn builder_custom_rule_subset_skips_proximity() {
+        let typo_only = TitleMatcher::builder()
+            .rules(vec![MatchRule::Typo])
+            .build();
+        let scores = typo_only.score_pair("Neural Networks", "Reordered Neural Networks Survey");
+        assert_eq!(scores.proximity, 0.0);
+        assert_eq!(scores.exactness, 0.0);
+    }
+
+    fn sample_corpus() -> Vec<String> {
+        vec![
+            "Attention Is All You Need".to_string(),
+            "Deep Residual Learning for Image Recognition".to_string(),
+            "Generative Adversarial Networks".to_string(),
+            "BERT: Pre-training of Deep Bidirectional Transformers".to_string(),
+        ]
+    }
+
+    #[test]
+    fn title_index_matches_linear_scan() {
+        let corpus = sample_corpus();
+        let index = TitleIndex::build(&corpus);
+        let matcher = TitleMatcher::default();
+
+        let query = "Attentoin is all you need";
+        let (indexed_idx, indexed_score) = index.best_match(query);
+        let (linear_idx, linear_score) = matcher.best_match(query, &corpus);
+
+        assert_eq!(indexed_idx, linear_idx);
+        assert!((indexed_score - linear_score).abs() < f64::EPSILON);
+        assert_eq!(indexed_idx, Some(0));
+    }
+
+    #[test]
+    fn title_index_falls_back_to_full_scan_with_no_shared_tokens() {
+        // Every token below is a typo of its counterpart in the corpus
+        // entry, so the inverted index finds zero exact-token candidates
+        // and must fall back to scoring the whole corpus.
+        let corpus = vec!["Generative Adversarial Networks".to_string()];
+        let index = TitleIndex::build(&corpus);
+        let (idx, score) = index.best_match("Generatve Adversarial Netwroks");
+        assert_eq!(idx, Some(0));
+        assert!(score > DEFAULT_MATCH_THRESHOLD);
+    }
+
+    #[test]
+    fn title_index_empty_query_returns_none() {
+        let index = TitleIndex::build(&sample_corpus());
+        assert_eq!(index.best_match("???"), (None, 0.0));
+    }
+
+    #[test]
+    fn title_index_len_and_title_lookup() {
+        let corpus = sample_corpus();
+        let index = TitleIndex::build(&corpus);
+        assert_eq!(index.len(), corpus.len());
+        assert!(!index.is_empty());
+        assert_eq!(index.title(0), Some("Attention Is All You Need"));
+        assert_eq!(index.title(99), None);
+    }
+
+    #[test]
+    fn title_index_min_shared_tokens_narrows_candidates() {
+        let corpus = sample_corpus();
+        let index = TitleIndex::build(&corpus).with_min_shared_tokens(2);
+        let (idx, _) = index.best_match("Attention Is All You Need");
+        assert_eq!(idx, Some(0));
+    }
+
+    #[test]
+    fn title_index_does_not_let_a_filler_token_mask_the_true_match() {
+        // The query is a heavily typo'd version of entry 0, sharing zero
+        // exact tokens with it, plus a stray "survey" token. Entry 1 shares
+        // that one filler token with the query and nothing else, so without
+        // a threshold check it would be the (wrong) sole candidate; the
+        // true match only turns up via the full-corpus fallback.
+        let corpus = vec![
+            "Neural Machine Translation".to_string(),
+            "Comprehensive Survey Of Deep Learning".to_string(),
+        ];
+        let index = TitleIndex::build(&corpus);
+        let matcher = TitleMatcher::default();
+
+        let query = "Survey Nueral Macine Tranlsation";
+        let (idx, score) = index.best_match_with(&matcher, query);
+
+        assert_eq!(idx, Some(0));
+        assert!(score >= DEFAULT_MATCH_THRESHOLD);
+    }
+}
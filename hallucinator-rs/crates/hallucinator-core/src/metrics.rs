@@ -0,0 +1,286 @@
+//! Per-database telemetry: request/hit/error counts and a latency
+//! distribution, updated by [`crate::rate_limit::query_with_backoff`] for
+//! every attempt so callers auditing a run can see which databases actually
+//! contributed matches and which were slow, rate-limited, or timing out —
+//! and tune [`crate::rate_limit::default_rate_limits`] accordingly.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Upper bound (milliseconds) of each latency bucket. The last bucket is an
+/// open-ended overflow for anything slower. Chosen to span a sub-second fast
+/// path up through a slow, near-timeout request without storing every raw
+/// sample.
+const BUCKET_BOUNDS_MS: &[u64] = &[50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000];
+
+/// What a single [`query_with_backoff`](crate::rate_limit::query_with_backoff)
+/// attempt resolved to, for the purposes of counting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryOutcome {
+    /// A matching record was found.
+    Hit,
+    /// The database was queried successfully but had no matching record.
+    NotFound,
+    /// The attempt failed with [`crate::db::DbQueryError::RateLimited`].
+    RateLimited,
+    /// The attempt failed with [`crate::db::DbQueryError::Timeout`].
+    Timeout,
+    /// Any other failure (HTTP error, parse error, network error, or the
+    /// circuit breaker being open).
+    Error,
+}
+
+/// Fixed-bucket latency histogram — cheap to update under a single lock,
+/// with enough resolution to report min/median/p95 without retaining every
+/// sample.
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    counts: [u64; BUCKET_BOUNDS_MS.len() + 1],
+    min_ms: Option<u64>,
+}
+
+impl Histogram {
+    fn record(&mut self, elapsed: Duration) {
+        let ms = elapsed.as_millis() as u64;
+        self.min_ms = Some(self.min_ms.map_or(ms, |m| m.min(ms)));
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|&bound| ms <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.counts[bucket] += 1;
+    }
+
+    fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Approximate the value at `quantile` (0.0..=1.0) as the upper bound of
+    /// the bucket that quantile falls into; the open-ended overflow bucket
+    /// reports the last named bound.
+    fn quantile_ms(&self, quantile: f64) -> Option<u64> {
+        let total = self.total();
+        if total == 0 {
+            return None;
+        }
+        let target = ((quantile * total as f64).ceil() as u64).max(1);
+        let mut cumulative = 0u64;
+        for (i, &count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Some(
+                    BUCKET_BOUNDS_MS
+                        .get(i)
+                        .copied()
+                        .unwrap_or_else(|| *BUCKET_BOUNDS_MS.last().unwrap()),
+                );
+            }
+        }
+        BUCKET_BOUNDS_MS.last().copied()
+    }
+
+    fn median_ms(&self) -> Option<u64> {
+        self.quantile_ms(0.5)
+    }
+
+    fn p95_ms(&self) -> Option<u64> {
+        self.quantile_ms(0.95)
+    }
+}
+
+/// Accumulated counters and latency distribution for a single database.
+#[derive(Debug, Clone, Default)]
+struct DbMetrics {
+    requests: u64,
+    hits: u64,
+    not_found: u64,
+    rate_limited: u64,
+    timeouts: u64,
+    errors: u64,
+    retries: u64,
+    latency: Histogram,
+}
+
+/// A point-in-time copy of [`DbMetrics`] for a single database, safe to hold
+/// onto and render without keeping the lock.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DbMetricsSnapshot {
+    pub requests: u64,
+    pub hits: u64,
+    pub not_found: u64,
+    pub rate_limited: u64,
+    pub timeouts: u64,
+    pub errors: u64,
+    pub retries: u64,
+    pub min_latency_ms: Option<u64>,
+    pub median_latency_ms: Option<u64>,
+    pub p95_latency_ms: Option<u64>,
+}
+
+impl DbMetricsSnapshot {
+    /// Fraction of requests that found a matching record, in `0.0..=1.0`.
+    /// `0.0` for a database that hasn't been queried yet.
+    pub fn hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.requests as f64
+        }
+    }
+}
+
+impl From<&DbMetrics> for DbMetricsSnapshot {
+    fn from(m: &DbMetrics) -> Self {
+        Self {
+            requests: m.requests,
+            hits: m.hits,
+            not_found: m.not_found,
+            rate_limited: m.rate_limited,
+            timeouts: m.timeouts,
+            errors: m.errors,
+            retries: m.retries,
+            min_latency_ms: m.latency.min_ms,
+            median_latency_ms: m.latency.median_ms(),
+            p95_latency_ms: m.latency.p95_ms(),
+        }
+    }
+}
+
+/// Per-database request/hit-rate/latency telemetry, updated by
+/// [`crate::rate_limit::query_with_backoff`] on every attempt. Threaded
+/// alongside an [`crate::rate_limit::RateLimiter`] (typically as an
+/// `Arc<Metrics>`) so a single run's state can be rendered into a
+/// "database performance" export section afterwards.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    per_db: Mutex<HashMap<String, DbMetrics>>,
+}
+
+impl Metrics {
+    /// Create an empty metrics store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of one query attempt against `db_name`, including
+    /// how long it took (wall-clock, including any time spent waiting on the
+    /// rate limiter).
+    pub async fn record_outcome(&self, db_name: &str, outcome: QueryOutcome, elapsed: Duration) {
+        let mut per_db = self.per_db.lock().await;
+        let m = per_db.entry(db_name.to_string()).or_default();
+        m.requests += 1;
+        m.latency.record(elapsed);
+        match outcome {
+            QueryOutcome::Hit => m.hits += 1,
+            QueryOutcome::NotFound => m.not_found += 1,
+            QueryOutcome::RateLimited => m.rate_limited += 1,
+            QueryOutcome::Timeout => m.timeouts += 1,
+            QueryOutcome::Error => m.errors += 1,
+        }
+    }
+
+    /// Record that `db_name` was retried after a transient failure.
+    pub async fn record_retry(&self, db_name: &str) {
+        let mut per_db = self.per_db.lock().await;
+        per_db.entry(db_name.to_string()).or_default().retries += 1;
+    }
+
+    /// Snapshot of `db_name`'s counters, or the default (all-zero) snapshot
+    /// if it has never been queried.
+    pub async fn snapshot(&self, db_name: &str) -> DbMetricsSnapshot {
+        let per_db = self.per_db.lock().await;
+        per_db
+            .get(db_name)
+            .map(DbMetricsSnapshot::from)
+            .unwrap_or_default()
+    }
+
+    /// Snapshots for every database seen so far, sorted by name for stable
+    /// export ordering.
+    pub async fn snapshot_all(&self) -> Vec<(String, DbMetricsSnapshot)> {
+        let per_db = self.per_db.lock().await;
+        let mut snapshots: Vec<(String, DbMetricsSnapshot)> = per_db
+            .iter()
+            .map(|(name, m)| (name.clone(), DbMetricsSnapshot::from(m)))
+            .collect();
+        snapshots.sort_by(|a, b| a.0.cmp(&b.0));
+        snapshots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn unseen_database_reports_zero_snapshot() {
+        let metrics = Metrics::new();
+        let snap = metrics.snapshot("DBLP").await;
+        assert_eq!(snap, DbMetricsSnapshot::default());
+        assert_eq!(snap.hit_rate(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn record_outcome_counts_by_kind() {
+        let metrics = Metrics::new();
+        metrics
+            .record_outcome("DBLP", QueryOutcome::Hit, Duration::from_millis(10))
+            .await;
+        metrics
+            .record_outcome("DBLP", QueryOutcome::NotFound, Duration::from_millis(20))
+            .await;
+        metrics
+            .record_outcome("DBLP", QueryOutcome::Timeout, Duration::from_millis(30))
+            .await;
+
+        let snap = metrics.snapshot("DBLP").await;
+        assert_eq!(snap.requests, 3);
+        assert_eq!(snap.hits, 1);
+        assert_eq!(snap.not_found, 1);
+        assert_eq!(snap.timeouts, 1);
+        assert_eq!(snap.hit_rate(), 1.0 / 3.0);
+    }
+
+    #[tokio::test]
+    async fn record_retry_increments_independent_of_outcome() {
+        let metrics = Metrics::new();
+        metrics.record_retry("CrossRef").await;
+        metrics.record_retry("CrossRef").await;
+        let snap = metrics.snapshot("CrossRef").await;
+        assert_eq!(snap.retries, 2);
+        assert_eq!(snap.requests, 0);
+    }
+
+    #[tokio::test]
+    async fn latency_quantiles_track_recorded_buckets() {
+        let metrics = Metrics::new();
+        for ms in [10, 40, 60, 200, 4_000] {
+            metrics
+                .record_outcome("DBLP", QueryOutcome::Hit, Duration::from_millis(ms))
+                .await;
+        }
+        let snap = metrics.snapshot("DBLP").await;
+        assert_eq!(snap.min_latency_ms, Some(10));
+        assert!(snap.median_latency_ms.unwrap() <= snap.p95_latency_ms.unwrap());
+        assert_eq!(snap.p95_latency_ms, Some(5_000));
+    }
+
+    #[tokio::test]
+    async fn snapshot_all_is_sorted_by_database_name() {
+        let metrics = Metrics::new();
+        metrics
+            .record_outcome("Semantic Scholar", QueryOutcome::Hit, Duration::from_millis(1))
+            .await;
+        metrics
+            .record_outcome("DBLP", QueryOutcome::Hit, Duration::from_millis(1))
+            .await;
+
+        let names: Vec<String> = metrics
+            .snapshot_all()
+            .await
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["DBLP".to_string(), "Semantic Scholar".to_string()]);
+    }
+}
@@ -1,18 +1,72 @@
-//! Per-database rate limiting and exponential backoff for 429 responses.
+//! Per-database rate limiting and decorrelated-jitter backoff for transient
+//! query failures (429s, timeouts, connection hiccups).
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
-use crate::db::{DatabaseBackend, DbQueryResult};
+use crate::db::{DatabaseBackend, DbQueryError, DbQueryResult};
+use crate::metrics::{Metrics, QueryOutcome};
 
-/// Enforces minimum intervals between requests to each database.
+/// Whether a caller should proceed with a query or skip it because the
+/// circuit breaker for that database is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Proceed with the query (circuit closed, half-open probe, or no
+    /// breaker state yet).
+    Proceed,
+    /// Circuit is open and the cooldown hasn't elapsed; skip this database
+    /// entirely and let other databases verify the reference instead.
+    Skip,
+}
+
+/// Per-database circuit breaker state.
+#[derive(Debug, Clone)]
+struct CircuitState {
+    status: CircuitStatus,
+    consecutive_failures: u32,
+    /// Cooldown to apply the *next* time the circuit opens; doubles on each
+    /// repeated trip (up to `MAX_COOLDOWN`) so a database that keeps failing
+    /// right after recovering gets backed off harder each time.
+    next_cooldown: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitStatus {
+    Closed,
+    /// Open until `Instant`, after which a single half-open probe is let
+    /// through.
+    Open(Instant),
+    /// A probe request is currently in flight; no more are let through until
+    /// it resolves.
+    HalfOpen,
+}
+
+impl Default for CircuitState {
+    fn default() -> Self {
+        Self {
+            status: CircuitStatus::Closed,
+            consecutive_failures: 0,
+            next_cooldown: INITIAL_COOLDOWN,
+        }
+    }
+}
+
+const FAILURE_THRESHOLD: u32 = 5;
+const INITIAL_COOLDOWN: Duration = Duration::from_secs(30);
+const MAX_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+
+/// Enforces minimum intervals between requests to each database, and trips a
+/// per-database circuit breaker after repeated failures so a dead or
+/// throttling backend isn't hammered on every reference.
 pub struct RateLimiter {
     /// Minimum interval between requests per database name.
     intervals: HashMap<String, Duration>,
     /// Last request time per database name.
     last_request: Mutex<HashMap<String, Instant>>,
+    /// Circuit breaker state per database name.
+    circuits: Mutex<HashMap<String, CircuitState>>,
 }
 
 impl RateLimiter {
@@ -21,6 +75,56 @@ impl RateLimiter {
         Self {
             intervals,
             last_request: Mutex::new(HashMap::new()),
+            circuits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check whether `db_name` should be queried right now. While the
+    /// circuit is open and the cooldown hasn't elapsed, returns
+    /// [`Admission::Skip`]. Otherwise returns [`Admission::Proceed`] — if the
+    /// circuit was open past its cooldown, this also transitions it to
+    /// half-open so only one probe is let through at a time.
+    pub async fn admit(&self, db_name: &str) -> Admission {
+        let mut circuits = self.circuits.lock().await;
+        let circuit = circuits.entry(db_name.to_string()).or_default();
+
+        match circuit.status {
+            CircuitStatus::Closed => Admission::Proceed,
+            CircuitStatus::HalfOpen => Admission::Skip,
+            CircuitStatus::Open(opened_until) => {
+                if Instant::now() < opened_until {
+                    Admission::Skip
+                } else {
+                    circuit.status = CircuitStatus::HalfOpen;
+                    Admission::Proceed
+                }
+            }
+        }
+    }
+
+    /// Record a successful query against `db_name`: resets the circuit to
+    /// closed and zeroes the failure counter and cooldown.
+    pub async fn record_success(&self, db_name: &str) {
+        let mut circuits = self.circuits.lock().await;
+        circuits.insert(db_name.to_string(), CircuitState::default());
+    }
+
+    /// Record a failed query against `db_name`. Past `FAILURE_THRESHOLD`
+    /// consecutive failures (or on a failed half-open probe), opens the
+    /// circuit for `next_cooldown`, then doubles `next_cooldown` (capped at
+    /// `MAX_COOLDOWN`) for the next trip.
+    pub async fn record_failure(&self, db_name: &str) {
+        let mut circuits = self.circuits.lock().await;
+        let circuit = circuits.entry(db_name.to_string()).or_default();
+
+        match circuit.status {
+            CircuitStatus::HalfOpen => trip(circuit),
+            _ => {
+                circuit.consecutive_failures += 1;
+                if circuit.consecutive_failures >= FAILURE_THRESHOLD {
+                    trip(circuit);
+                }
+            }
         }
     }
 
@@ -67,6 +171,13 @@ impl RateLimiter {
     }
 }
 
+/// Open `circuit` for its current `next_cooldown`, then double
+/// `next_cooldown` (capped at `MAX_COOLDOWN`) for the next trip.
+fn trip(circuit: &mut CircuitState) {
+    circuit.status = CircuitStatus::Open(Instant::now() + circuit.next_cooldown);
+    circuit.next_cooldown = (circuit.next_cooldown * 2).min(MAX_COOLDOWN);
+}
+
 /// Default rate limit intervals.
 pub fn default_rate_limits() -> HashMap<String, Duration> {
     let mut m = HashMap::new();
@@ -76,48 +187,216 @@ pub fn default_rate_limits() -> HashMap<String, Duration> {
 
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
-/// Query a database with exponential backoff on 429 errors.
+/// Query a database with decorrelated-jitter backoff on transient failures
+/// (429s, timeouts, connection hiccups), short-circuiting via a per-database
+/// circuit breaker when the database has recently failed too many times in a
+/// row.
+///
+/// Retries up to `MAX_RETRIES` times. When the server sends a `Retry-After`
+/// header (surfaced via [`DbQueryError::RateLimited`]), that duration is
+/// used verbatim; otherwise the next wait is drawn via decorrelated jitter —
+/// `next = min(MAX_BACKOFF, rand(INITIAL_BACKOFF..=prev * 3))` — so that many
+/// concurrent tasks hitting the same database don't retry in lockstep the
+/// way fixed exponential doubling does.
+///
+/// Before attempting anything, consults [`RateLimiter::admit`]: if the
+/// circuit for this database is open, returns
+/// [`DbQueryError::Unavailable`] immediately without touching the network,
+/// so other databases can still verify the reference.
 ///
-/// Retries up to `MAX_RETRIES` times with doubling delays (1s, 2s, 4s),
-/// bounded by the overall `timeout`.
+/// Every attempt (including retries) is timed and recorded in `metrics`, so
+/// the reported latency reflects wall-clock time spent waiting on the rate
+/// limiter as well as the query itself.
 pub async fn query_with_backoff(
     db: &Arc<dyn DatabaseBackend>,
     title: &str,
     client: &reqwest::Client,
     timeout: Duration,
     rate_limiter: &Arc<RateLimiter>,
-) -> Result<DbQueryResult, String> {
+    metrics: &Arc<Metrics>,
+) -> Result<DbQueryResult, DbQueryError> {
     let db_name = db.name();
-    let mut backoff = INITIAL_BACKOFF;
+
+    if rate_limiter.admit(db_name).await == Admission::Skip {
+        log::warn!("{db_name}: circuit breaker open, skipping");
+        return Err(DbQueryError::Unavailable);
+    }
+
+    let mut prev_backoff = INITIAL_BACKOFF;
 
     for attempt in 0..=MAX_RETRIES {
         rate_limiter.acquire(db_name).await;
 
+        let started = Instant::now();
         let result = db.query(title, client, timeout).await;
+        let elapsed = started.elapsed();
+        metrics.record_outcome(db_name, classify(&result), elapsed).await;
 
         match &result {
-            Err(e) if attempt < MAX_RETRIES && is_rate_limited(e) => {
+            Ok(_) => {
+                rate_limiter.record_success(db_name).await;
+                return result;
+            }
+            Err(e) if attempt < MAX_RETRIES && is_retryable(e) => {
+                let wait = match e {
+                    DbQueryError::RateLimited {
+                        retry_after: Some(retry_after),
+                    } => *retry_after,
+                    _ => decorrelated_jitter(prev_backoff),
+                };
                 log::warn!(
-                    "{}: rate limited (429), retrying in {:?} (attempt {}/{})",
+                    "{}: {} (retryable), retrying in {:?} (attempt {}/{})",
                     db_name,
-                    backoff,
+                    e,
+                    wait,
                     attempt + 1,
                     MAX_RETRIES
                 );
-                // Push back the rate limiter so concurrent tasks also wait
-                rate_limiter.record_backoff(db_name, backoff).await;
-                tokio::time::sleep(backoff).await;
-                backoff *= 2;
+                // Push back the rate limiter so concurrent tasks also wait.
+                rate_limiter.record_backoff(db_name, wait).await;
+                metrics.record_retry(db_name).await;
+                tokio::time::sleep(wait).await;
+                prev_backoff = wait;
+            }
+            Err(_) => {
+                rate_limiter.record_failure(db_name).await;
+                return result;
             }
-            _ => return result,
         }
     }
 
+    rate_limiter.record_failure(db_name).await;
     // Unreachable, but satisfy the compiler
-    Err(format!("{}: max retries exceeded", db_name))
+    Err(DbQueryError::Other(format!(
+        "{db_name}: max retries exceeded"
+    )))
+}
+
+/// Classify a query attempt's result into a [`QueryOutcome`] for metrics
+/// purposes.
+fn classify(result: &Result<DbQueryResult, DbQueryError>) -> QueryOutcome {
+    match result {
+        Ok((Some(_), _, _)) => QueryOutcome::Hit,
+        Ok((None, _, _)) => QueryOutcome::NotFound,
+        Err(DbQueryError::RateLimited { .. }) => QueryOutcome::RateLimited,
+        Err(DbQueryError::Timeout) => QueryOutcome::Timeout,
+        Err(_) => QueryOutcome::Error,
+    }
+}
+
+/// Decorrelated jitter (as used by AWS's backoff guidance): the next wait is
+/// a random duration between `INITIAL_BACKOFF` and three times the previous
+/// wait, capped at `MAX_BACKOFF`. Spreads out concurrent retries instead of
+/// having every caller double in lockstep.
+fn decorrelated_jitter(prev_backoff: Duration) -> Duration {
+    let upper = (prev_backoff * 3).min(MAX_BACKOFF);
+    if upper <= INITIAL_BACKOFF {
+        return upper;
+    }
+    let jittered_ms = rand::random_range(INITIAL_BACKOFF.as_millis() as u64..=upper.as_millis() as u64);
+    Duration::from_millis(jittered_ms)
+}
+
+/// Whether `error` is worth retrying: rate limits, timeouts, and
+/// connection-level network hiccups are transient the same way a refused or
+/// reset connection is — a genuinely malformed response or permanent HTTP
+/// error is not.
+fn is_retryable(error: &DbQueryError) -> bool {
+    error.is_transient()
 }
 
-fn is_rate_limited(error: &str) -> bool {
-    error.contains("429") || error.contains("rate limit")
+/// Inspect an HTTP response for a 429 status, returning
+/// [`DbQueryError::RateLimited`] (with `Retry-After` parsed, if present) so
+/// [`query_with_backoff`] can react without string-matching the body.
+pub fn check_rate_limit_response(resp: &reqwest::Response) -> Result<(), DbQueryError> {
+    if resp.status().as_u16() != 429 {
+        return Ok(());
+    }
+    let retry_after = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after);
+    Err(DbQueryError::RateLimited { retry_after })
+}
+
+/// Parse a `Retry-After` header value, either as an integer number of
+/// seconds (the common case) or an RFC 7231 HTTP-date (the less common
+/// case some servers use instead).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let target = httpdate::parse_http_date(value.trim()).ok()?;
+    target
+        .duration_since(std::time::SystemTime::now())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn circuit_starts_closed() {
+        let limiter = RateLimiter::new(HashMap::new());
+        assert_eq!(limiter.admit("DBLP").await, Admission::Proceed);
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_after_threshold_failures() {
+        let limiter = RateLimiter::new(HashMap::new());
+        for _ in 0..FAILURE_THRESHOLD {
+            limiter.record_failure("DBLP").await;
+        }
+        assert_eq!(limiter.admit("DBLP").await, Admission::Skip);
+    }
+
+    #[tokio::test]
+    async fn circuit_stays_closed_below_threshold() {
+        let limiter = RateLimiter::new(HashMap::new());
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            limiter.record_failure("DBLP").await;
+        }
+        assert_eq!(limiter.admit("DBLP").await, Admission::Proceed);
+    }
+
+    #[tokio::test]
+    async fn half_open_probe_failure_reopens_circuit() {
+        let limiter = RateLimiter::new(HashMap::new());
+        for _ in 0..FAILURE_THRESHOLD {
+            limiter.record_failure("DBLP").await;
+        }
+        {
+            let mut circuits = limiter.circuits.lock().await;
+            circuits.get_mut("DBLP").unwrap().status = CircuitStatus::HalfOpen;
+        }
+        limiter.record_failure("DBLP").await;
+        assert_eq!(limiter.admit("DBLP").await, Admission::Skip);
+    }
+
+    #[tokio::test]
+    async fn success_resets_circuit_to_closed() {
+        let limiter = RateLimiter::new(HashMap::new());
+        for _ in 0..FAILURE_THRESHOLD {
+            limiter.record_failure("DBLP").await;
+        }
+        limiter.record_success("DBLP").await;
+        assert_eq!(limiter.admit("DBLP").await, Admission::Proceed);
+
+        let circuits = limiter.circuits.lock().await;
+        assert_eq!(circuits["DBLP"].consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn independent_databases_have_independent_circuits() {
+        let limiter = RateLimiter::new(HashMap::new());
+        for _ in 0..FAILURE_THRESHOLD {
+            limiter.record_failure("DBLP").await;
+        }
+        assert_eq!(limiter.admit("DBLP").await, Admission::Skip);
+        assert_eq!(limiter.admit("CrossRef").await, Admission::Proceed);
+    }
 }
@@ -1,8 +1,11 @@
 //! Download and build pipeline for the offline DBLP database.
 
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::Mutex;
 
 use flate2::read::GzDecoder;
 use rusqlite::Connection;
@@ -17,6 +20,87 @@ pub const DEFAULT_DBLP_URL: &str = "https://dblp.org/rdf/dblp.nt.gz";
 /// Default batch size for database inserts.
 const BATCH_SIZE: usize = 10_000;
 
+/// Raw lines are read and handed to parser workers in chunks this size, so
+/// workers do a useful unit of work per channel round-trip.
+const LINE_CHUNK_SIZE: usize = 8_000;
+
+/// Bounded channel capacity (in chunks/batches) between pipeline stages.
+/// Keeps memory flat under backpressure instead of buffering the whole dump.
+const CHANNEL_CAPACITY: usize = 8;
+
+/// Environment variable overriding the worker pool size (parse stage only).
+/// Falls back to available parallelism, same resolution order CLI flags use
+/// elsewhere in this project (explicit override > env var > default).
+const WORKER_COUNT_ENV: &str = "DBLP_BUILD_WORKERS";
+
+fn worker_count_from_env() -> usize {
+    std::env::var(WORKER_COUNT_ENV)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        })
+}
+
+/// zstd magic number: <https://github.com/facebook/zstd/blob/dev/doc/zstd_compression_format.md#zstandard-frames>
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+/// gzip magic number (RFC 1952).
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// zstd compression level used for the on-disk triple cache — matches the
+/// fast/ratio tradeoff this project's other large streaming paths use.
+const TRIPLE_CACHE_ZSTD_LEVEL: i32 = 3;
+
+/// Sniff the compression codec from the first bytes of `r` and wrap it in
+/// the matching decompressing reader. Used for the HTTP download path, where
+/// there's no file extension to go by.
+fn detect_and_wrap<R: Read + Send + 'static>(mut r: R) -> Result<Box<dyn Read + Send>, DblpError> {
+    let mut magic = [0u8; 4];
+    let mut filled = 0;
+    while filled < magic.len() {
+        let n = r.read(&mut magic[filled..])?;
+        if n == 0 {
+            break;
+        }
+        filled += n;
+    }
+    let prefix = std::io::Cursor::new(magic[..filled].to_vec());
+    let chained = prefix.chain(r);
+
+    if filled >= 2 && magic[..2] == GZIP_MAGIC {
+        Ok(Box::new(GzDecoder::new(chained)))
+    } else if filled >= 4 && magic == ZSTD_MAGIC {
+        Ok(Box::new(zstd::Decoder::new(chained)?))
+    } else {
+        Err(DblpError::Download(
+            "unrecognized compression format (expected gzip or zstd magic bytes)".to_string(),
+        ))
+    }
+}
+
+/// Open a decompressing reader for a local dump file, choosing the codec
+/// from its extension: `.nt.zst` → zstd, anything else → gzip (matching the
+/// long-standing default).
+fn open_decoder_for_file(path: &Path) -> Result<Box<dyn Read + Send>, DblpError> {
+    let file = File::open(path)?;
+    if path.extension().is_some_and(|ext| ext == "zst") {
+        Ok(Box::new(zstd::Decoder::new(file)?))
+    } else {
+        Ok(Box::new(GzDecoder::new(file)))
+    }
+}
+
+/// Path of the cached, decompressed-and-filtered triple stream kept next to
+/// the database so a schema bump can reparse without re-downloading.
+fn triple_cache_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_os_string();
+    name.push(".triples.nt.zst");
+    PathBuf::from(name)
+}
+
 /// Build (or update) the offline DBLP database by downloading from dblp.org.
 ///
 /// Uses ETag/Last-Modified headers for conditional requests — if the remote
@@ -28,8 +112,9 @@ pub fn build(
     db_path: &Path,
     mut progress: impl FnMut(BuildProgress),
 ) -> Result<bool, DblpError> {
-    let conn = Connection::open(db_path)?;
+    let mut conn = Connection::open(db_path)?;
     db::init_database(&conn)?;
+    db::migrate(&mut conn)?;
 
     // Check stored ETag/Last-Modified
     let stored_etag = db::get_metadata(&conn, "etag")?;
@@ -89,20 +174,26 @@ pub fn build(
 
     let total_bytes = response.content_length();
 
-    // Stream response through gzip decompression
-    let decoder = GzDecoder::new(response);
+    // Sniff gzip vs zstd from the stream's magic bytes — lets users point
+    // --update-dblp at a smaller zstd mirror instead of the canonical .nt.gz.
+    let decoder = detect_and_wrap(response)?;
     let reader = BufReader::with_capacity(1024 * 1024, decoder);
 
-    process_lines(&conn, reader, total_bytes, &mut progress)?;
+    process_lines(&conn, reader, total_bytes, &triple_cache_path(db_path), &mut progress)?;
 
     // Rebuild FTS index
     progress(BuildProgress::RebuildingIndex);
     db::rebuild_fts_index(&conn)?;
 
+    // Keep the query planner's statistics fresh after millions of inserts —
+    // cheap enough to run unconditionally after every build, unlike the
+    // VACUUM a user has to opt into separately via `run_maintenance`.
+    db::optimize(&conn)?;
+    db::analyze(&conn)?;
+
     // Update metadata
     let timestamp = now_rfc3339();
     db::set_metadata(&conn, "last_updated", &timestamp)?;
-    db::set_metadata(&conn, "schema_version", "2")?;
     if let Some(etag) = new_etag {
         db::set_metadata(&conn, "etag", &etag)?;
     }
@@ -123,36 +214,43 @@ pub fn build(
     Ok(true)
 }
 
-/// Build the offline DBLP database from a local `.nt.gz` file.
+/// Build the offline DBLP database from a local dump file.
+///
+/// Accepts either `.nt.gz` or `.nt.zst` (detected from the file extension).
 pub fn build_from_file(
     db_path: &Path,
     nt_gz_path: &Path,
     mut progress: impl FnMut(BuildProgress),
 ) -> Result<(), DblpError> {
-    let conn = Connection::open(db_path)?;
+    let mut conn = Connection::open(db_path)?;
     db::init_database(&conn)?;
+    db::migrate(&mut conn)?;
 
-    let file = File::open(nt_gz_path)?;
-    let file_size = file.metadata().map(|m| m.len()).ok();
+    let file_size = std::fs::metadata(nt_gz_path).map(|m| m.len()).ok();
 
     progress(BuildProgress::Parsing {
         lines_processed: 0,
         records_inserted: 0,
     });
 
-    let decoder = GzDecoder::new(file);
+    let decoder = open_decoder_for_file(nt_gz_path)?;
     let reader = BufReader::with_capacity(1024 * 1024, decoder);
 
-    process_lines(&conn, reader, file_size, &mut progress)?;
+    process_lines(&conn, reader, file_size, &triple_cache_path(db_path), &mut progress)?;
 
     // Rebuild FTS index
     progress(BuildProgress::RebuildingIndex);
     db::rebuild_fts_index(&conn)?;
 
+    // Keep the query planner's statistics fresh after millions of inserts —
+    // cheap enough to run unconditionally after every build, unlike the
+    // VACUUM a user has to opt into separately via `run_maintenance`.
+    db::optimize(&conn)?;
+    db::analyze(&conn)?;
+
     // Update metadata
     let timestamp = now_rfc3339();
     db::set_metadata(&conn, "last_updated", &timestamp)?;
-    db::set_metadata(&conn, "schema_version", "2")?;
 
     let (pubs, authors, _) = db::get_counts(&conn)?;
     db::set_metadata(&conn, "publication_count", &pubs.to_string())?;
@@ -168,70 +266,203 @@ pub fn build_from_file(
 }
 
 /// Process lines from a buffered reader, routing triples into batch inserts.
-fn process_lines<R: BufRead>(
+///
+/// Runs a producer/consumer pipeline instead of a single-threaded loop:
+/// this thread owns `reader` and a dedicated reader thread chunks raw lines
+/// off of it; a pool of worker threads (sized by [`worker_count_from_env`])
+/// call `parser::parse_line` and build per-worker [`InsertBatch`]es; this
+/// thread — the only one that ever touches `conn`, since SQLite allows a
+/// single writer — drains completed batches as they arrive. Bounded channels
+/// between every stage provide backpressure so memory stays flat regardless
+/// of dump size.
+fn process_lines<R: BufRead + Send>(
     conn: &Connection,
     reader: R,
     _total_bytes: Option<u64>,
+    cache_path: &Path,
     progress: &mut impl FnMut(BuildProgress),
 ) -> Result<(), DblpError> {
-    let mut batch = InsertBatch::new();
-    let mut lines_processed: u64 = 0;
-    let mut records_inserted: u64 = 0;
-
-    for line_result in reader.lines() {
-        let line = line_result?;
-        lines_processed += 1;
-
-        if lines_processed % 100_000 == 0 {
-            progress(BuildProgress::Parsing {
-                lines_processed,
-                records_inserted,
-            });
-        }
-
-        let triple = match parser::parse_line(&line) {
-            Some(t) => t,
-            None => continue,
-        };
+    process_lines_with_workers(
+        conn,
+        reader,
+        _total_bytes,
+        cache_path,
+        progress,
+        worker_count_from_env(),
+    )
+}
 
-        // Route triple by predicate
-        match triple.predicate.as_str() {
-            parser::TITLE | parser::DC_TITLE => {
-                if !triple.object_is_uri {
-                    batch.publications.push((triple.subject, triple.object));
+/// Same as [`process_lines`] but with an explicit worker count, so tests can
+/// pin it (e.g. to 1) for deterministic batch ordering.
+#[allow(clippy::too_many_arguments)]
+fn process_lines_with_workers<R: BufRead + Send>(
+    conn: &Connection,
+    reader: R,
+    _total_bytes: Option<u64>,
+    cache_path: &Path,
+    progress: &mut impl FnMut(BuildProgress),
+    worker_count: usize,
+) -> Result<(), DblpError> {
+    let worker_count = worker_count.max(1);
+    let lines_processed = AtomicU64::new(0);
+    let mut records_inserted: u64 = 0;
+    let mut last_reported: u64 = 0;
+
+    // The writer thread also maintains a zstd-compressed cache of every line
+    // that survived filtering, so a later schema bump can reparse from disk
+    // instead of re-downloading the ~2GB dump.
+    let mut triple_cache = zstd::Encoder::new(File::create(cache_path)?, TRIPLE_CACHE_ZSTD_LEVEL)?;
+
+    let (chunk_tx, chunk_rx): (SyncSender<Vec<String>>, Receiver<Vec<String>>) =
+        sync_channel(CHANNEL_CAPACITY);
+    let chunk_rx = Mutex::new(chunk_rx);
+
+    let (batch_tx, batch_rx): (
+        SyncSender<(InsertBatch, Vec<String>)>,
+        Receiver<(InsertBatch, Vec<String>)>,
+    ) = sync_channel(CHANNEL_CAPACITY);
+
+    std::thread::scope(|scope| -> Result<(), DblpError> {
+        // Reader thread: owns the BufRead, chunks raw lines for the workers.
+        let reader_handle = scope.spawn(move || -> Result<(), DblpError> {
+            let mut reader = reader;
+            let mut chunk = Vec::with_capacity(LINE_CHUNK_SIZE);
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line)? == 0 {
+                    break;
                 }
-            }
-            parser::AUTHORED_BY => {
-                if triple.object_is_uri {
-                    batch
-                        .publication_authors
-                        .push((triple.subject, triple.object));
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
                 }
-            }
-            parser::PRIMARY_CREATOR_NAME | parser::CREATOR_NAME => {
-                if !triple.object_is_uri {
-                    batch.authors.push((triple.subject, triple.object));
+                chunk.push(line);
+                if chunk.len() >= LINE_CHUNK_SIZE {
+                    if chunk_tx.send(std::mem::replace(
+                        &mut chunk,
+                        Vec::with_capacity(LINE_CHUNK_SIZE),
+                    ))
+                    .is_err()
+                    {
+                        return Ok(());
+                    }
                 }
             }
-            _ => {}
-        }
+            if !chunk.is_empty() {
+                let _ = chunk_tx.send(chunk);
+            }
+            Ok(())
+        });
+
+        // Worker threads: parse lines, route triples, flush completed batches
+        // to the writer once they hit BATCH_SIZE.
+        let worker_handles: Vec<_> = (0..worker_count)
+            .map(|_| {
+                let chunk_rx = &chunk_rx;
+                let batch_tx = batch_tx.clone();
+                let lines_processed = &lines_processed;
+                scope.spawn(move || {
+                    let mut batch = InsertBatch::new();
+                    let mut kept_lines: Vec<String> = Vec::new();
+                    loop {
+                        let chunk = {
+                            let rx = chunk_rx.lock().expect("chunk channel mutex poisoned");
+                            rx.recv()
+                        };
+                        let Ok(chunk) = chunk else { break };
+                        let chunk_len = chunk.len() as u64;
+
+                        for line in chunk {
+                            let triple = match parser::parse_line(&line) {
+                                Some(t) => t,
+                                None => continue,
+                            };
+                            let mut kept = false;
+                            match triple.predicate.as_str() {
+                                parser::TITLE | parser::DC_TITLE => {
+                                    if !triple.object_is_uri {
+                                        batch.publications.push((triple.subject, triple.object));
+                                        kept = true;
+                                    }
+                                }
+                                parser::AUTHORED_BY => {
+                                    if triple.object_is_uri {
+                                        batch
+                                            .author_links
+                                            .push((triple.subject, triple.object));
+                                        kept = true;
+                                    }
+                                }
+                                parser::PRIMARY_CREATOR_NAME | parser::CREATOR_NAME => {
+                                    if !triple.object_is_uri {
+                                        batch.authors.push((triple.subject, triple.object));
+                                        kept = true;
+                                    }
+                                }
+                                _ => {}
+                            }
+                            if kept {
+                                kept_lines.push(line);
+                            }
+                        }
+                        lines_processed.fetch_add(chunk_len, Ordering::Relaxed);
+
+                        if batch.len() >= BATCH_SIZE {
+                            let full_batch = std::mem::take(&mut batch);
+                            let full_lines = std::mem::take(&mut kept_lines);
+                            if batch_tx.send((full_batch, full_lines)).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    if !batch.is_empty() || !kept_lines.is_empty() {
+                        let _ = batch_tx.send((batch, kept_lines));
+                    }
+                })
+            })
+            .collect();
 
-        // Flush batch when full
-        if batch.len() >= BATCH_SIZE {
+        // Drop our handles to the sending ends: reader_handle owns chunk_tx,
+        // and every worker holds its own clone of batch_tx, so once all of
+        // those finish, batch_rx below naturally drains and closes.
+        drop(batch_tx);
+
+        // Writer loop: this thread is the only one that ever touches `conn`
+        // (and the triple cache file).
+        for (batch, lines) in batch_rx {
             records_inserted += batch.len() as u64;
             db::insert_batch(conn, &batch)?;
-            batch.clear();
+            for line in &lines {
+                triple_cache.write_all(line.as_bytes())?;
+                triple_cache.write_all(b"\n")?;
+            }
+
+            let seen = lines_processed.load(Ordering::Relaxed);
+            if seen - last_reported >= 100_000 {
+                last_reported = seen;
+                progress(BuildProgress::Parsing {
+                    lines_processed: seen,
+                    records_inserted,
+                });
+            }
         }
-    }
 
-    // Flush remaining
-    if !batch.is_empty() {
-        records_inserted += batch.len() as u64;
-        db::insert_batch(conn, &batch)?;
-    }
+        for handle in worker_handles {
+            handle.join().expect("dblp build worker thread panicked");
+        }
+        reader_handle
+            .join()
+            .expect("dblp build reader thread panicked")?;
+
+        Ok(())
+    })?;
+
+    triple_cache.finish()?;
 
     progress(BuildProgress::Parsing {
-        lines_processed,
+        lines_processed: lines_processed.load(Ordering::Relaxed),
         records_inserted,
     });
 
@@ -274,6 +505,58 @@ mod tests {
         encoder.finish().unwrap()
     }
 
+    fn create_test_nt_zst() -> Vec<u8> {
+        let data = r#"<https://dblp.org/rec/conf/test/Paper1> <https://dblp.org/rdf/schema#title> "Test Paper One" .
+<https://dblp.org/pid/00/1> <https://dblp.org/rdf/schema#primaryCreatorName> "Alice Smith" .
+<https://dblp.org/rec/conf/test/Paper1> <https://dblp.org/rdf/schema#authoredBy> <https://dblp.org/pid/00/1> .
+"#;
+        zstd::encode_all(data.as_bytes(), 3).unwrap()
+    }
+
+    #[test]
+    fn test_build_from_zst_bytes() {
+        let zst_data = create_test_nt_zst();
+
+        let dir = tempfile::tempdir().unwrap();
+        let nt_zst_path = dir.path().join("test.nt.zst");
+        let db_path = dir.path().join("test.db");
+
+        std::fs::write(&nt_zst_path, &zst_data).unwrap();
+
+        build_from_file(&db_path, &nt_zst_path, |_| {}).unwrap();
+
+        let conn = Connection::open(&db_path).unwrap();
+        let (pubs, authors, rels) = db::get_counts(&conn).unwrap();
+        assert_eq!(pubs, 1);
+        assert_eq!(authors, 1);
+        assert_eq!(rels, 1);
+
+        // The filtered triple stream was cached alongside the DB as zstd.
+        let cache_path = triple_cache_path(&db_path);
+        assert!(cache_path.exists());
+        let cached = zstd::decode_all(File::open(&cache_path).unwrap()).unwrap();
+        assert_eq!(String::from_utf8(cached).unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_detect_and_wrap_sniffs_gzip_and_zstd() {
+        let gz_data = create_test_nt_gz();
+        let mut out = String::new();
+        detect_and_wrap(std::io::Cursor::new(gz_data))
+            .unwrap()
+            .read_to_string(&mut out)
+            .unwrap();
+        assert!(out.contains("Test Paper One"));
+
+        let zst_data = create_test_nt_zst();
+        let mut out = String::new();
+        detect_and_wrap(std::io::Cursor::new(zst_data))
+            .unwrap()
+            .read_to_string(&mut out)
+            .unwrap();
+        assert!(out.contains("Test Paper One"));
+    }
+
     #[test]
     fn test_build_from_gz_bytes() {
         let gz_data = create_test_nt_gz();
@@ -300,7 +583,7 @@ mod tests {
 
         // Verify metadata was set
         let schema = db::get_metadata(&conn, "schema_version").unwrap();
-        assert_eq!(schema, Some("2".into()));
+        assert_eq!(schema, Some(db::CURRENT_SCHEMA_VERSION.to_string()));
 
         let last_updated = db::get_metadata(&conn, "last_updated").unwrap();
         assert!(last_updated.is_some());
@@ -333,12 +616,48 @@ mod tests {
 <https://dblp.org/rec/1> <https://dblp.org/rdf/schema#authoredBy> <https://dblp.org/pid/1> .
 <https://dblp.org/rec/1> <http://purl.org/dc/terms/title> "Alt Title" .
 "#;
+        let dir = tempfile::tempdir().unwrap();
         let reader = BufReader::new(data.as_bytes());
-        process_lines(&conn, reader, None, &mut |_| {}).unwrap();
+        process_lines(&conn, reader, None, &dir.path().join("cache.nt.zst"), &mut |_| {}).unwrap();
 
         let (pubs, authors, rels) = db::get_counts(&conn).unwrap();
         assert_eq!(pubs, 1); // Two titles for same URI → UPSERT keeps one
         assert_eq!(authors, 1);
         assert_eq!(rels, 1);
     }
+
+    #[test]
+    fn test_process_lines_with_multiple_workers() {
+        // Same fixture as above, but forced through several parser workers to
+        // make sure the fan-out pipeline produces identical results whether
+        // triples for a record are parsed by one worker or split across many.
+        let conn = Connection::open_in_memory().unwrap();
+        db::init_database(&conn).unwrap();
+
+        let mut data = String::new();
+        for i in 0..50 {
+            data.push_str(&format!(
+                "<https://dblp.org/rec/{i}> <https://dblp.org/rdf/schema#title> \"Paper {i}\" .\n\
+                 <https://dblp.org/pid/{i}> <https://dblp.org/rdf/schema#creatorName> \"Author {i}\" .\n\
+                 <https://dblp.org/rec/{i}> <https://dblp.org/rdf/schema#authoredBy> <https://dblp.org/pid/{i}> .\n"
+            ));
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let reader = BufReader::new(data.as_bytes());
+        process_lines_with_workers(
+            &conn,
+            reader,
+            None,
+            &dir.path().join("cache.nt.zst"),
+            &mut |_| {},
+            4,
+        )
+        .unwrap();
+
+        let (pubs, authors, rels) = db::get_counts(&conn).unwrap();
+        assert_eq!(pubs, 50);
+        assert_eq!(authors, 50);
+        assert_eq!(rels, 50);
+    }
 }
@@ -1,9 +1,391 @@
 //! SQLite database operations for DBLP indexing.
 
-use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::path::Path;
+
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
 
 use crate::DblpError;
 
+/// Maximum number of FTS5 candidate rows pulled in for re-ranking by
+/// [`search_titles`]. Keeps the fuzzy pass bounded regardless of how broad
+/// the OR-ed recall query ends up being.
+const SEARCH_CANDIDATE_LIMIT: usize = 500;
+
+/// Per-word typo tolerance, scaled by word length: short words are one typo
+/// away from meaning something else, so we don't loosen matching for them.
+fn allowed_typos(word: &str) -> usize {
+    match word.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Lowercase, alphanumeric-run tokenization shared by query and candidate
+/// titles so typo distances are computed on comparable tokens.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Escape a token for embedding in an FTS5 `MATCH` string: quote it so stray
+/// punctuation left over from tokenization can't be parsed as query syntax.
+pub(crate) fn escape_fts_token(token: &str) -> String {
+    format!("\"{}\"", token.replace('"', "\"\""))
+}
+
+/// Classic Levenshtein edit distance, used to score typo-tolerant matches.
+/// Titles and query words are short, so the O(n*m) table is negligible.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let mut prev: Vec<usize> = (0..=m).collect();
+    let mut curr = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        curr[0] = i;
+        for j in 1..=m {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[m]
+}
+
+/// Titles with fewer tokens than this are too short for a 64-bit SimHash
+/// fingerprint to discriminate reliably (a single differing token can flip
+/// half the bits), so [`search_titles_by_simhash`] falls back to
+/// [`search_titles`] for them instead.
+const MIN_TOKENS_FOR_SIMHASH: usize = 4;
+
+/// Maximum Hamming distance between 64-bit SimHash fingerprints still
+/// considered a near-duplicate match, by default. A handful of bits tolerates
+/// a word or two of OCR noise without matching unrelated titles.
+pub const DEFAULT_SIMHASH_MAX_DISTANCE: u32 = 3;
+
+/// Hash one token to 64 bits. Not cryptographic — SimHash only needs the
+/// bits to be well-distributed and stable across calls, which
+/// [`DefaultHasher`](std::collections::hash_map::DefaultHasher) provides.
+fn hash_token(token: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    token.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 64-bit SimHash fingerprint of `title`: each distinct token votes on every
+/// bit of its hash (weighted by how many times it appears), and a bit is set
+/// in the result if more tokens voted for it than against. Titles that share
+/// most of their tokens end up with fingerprints a small Hamming distance
+/// apart, even if a few tokens differ (OCR noise, a missing subtitle, ...).
+pub(crate) fn simhash64(title: &str) -> i64 {
+    let tokens = tokenize(title);
+    if tokens.is_empty() {
+        return 0;
+    }
+
+    let mut counts: std::collections::HashMap<&str, i64> = std::collections::HashMap::new();
+    for token in &tokens {
+        *counts.entry(token.as_str()).or_insert(0) += 1;
+    }
+
+    let mut votes = [0i64; 64];
+    for (token, weight) in counts {
+        let hash = hash_token(token);
+        for (bit, vote) in votes.iter_mut().enumerate() {
+            if (hash >> bit) & 1 == 1 {
+                *vote += weight;
+            } else {
+                *vote -= weight;
+            }
+        }
+    }
+
+    let mut fingerprint: u64 = 0;
+    for (bit, &vote) in votes.iter().enumerate() {
+        if vote > 0 {
+            fingerprint |= 1 << bit;
+        }
+    }
+    fingerprint as i64
+}
+
+/// Number of differing bits between two SimHash fingerprints — the fewer,
+/// the more similar the titles that produced them.
+pub(crate) fn hamming_distance(a: i64, b: i64) -> u32 {
+    ((a as u64) ^ (b as u64)).count_ones()
+}
+
+/// Best match for one query word against a candidate's title tokens.
+struct WordMatch {
+    /// Index of the matched token within the candidate title (for proximity).
+    position: usize,
+    /// Edit distance incurred (0 for an exact hit).
+    typos: usize,
+    /// Whether the match was a whole-word hit rather than a prefix hit.
+    exact: bool,
+}
+
+/// Find the best-scoring title token for `query_word`, if any is within its
+/// typo budget (or is a prefix match, which we always allow but rank below
+/// edit-distance hits of the same cost).
+fn best_word_match(query_word: &str, title_tokens: &[String]) -> Option<WordMatch> {
+    let budget = allowed_typos(query_word);
+    let mut best: Option<WordMatch> = None;
+
+    for (position, token) in title_tokens.iter().enumerate() {
+        if token == query_word {
+            return Some(WordMatch {
+                position,
+                typos: 0,
+                exact: true,
+            });
+        }
+
+        let dist = levenshtein(query_word, token);
+        if dist <= budget {
+            let candidate = WordMatch {
+                position,
+                typos: dist,
+                exact: false,
+            };
+            if best.as_ref().is_none_or(|b| candidate.typos < b.typos) {
+                best = Some(candidate);
+            }
+        } else if token.starts_with(query_word.as_str()) || query_word.starts_with(token.as_str())
+        {
+            // Prefix hit: rank worse than any edit-distance hit of equal
+            // length, but still better than no match at all.
+            let candidate = WordMatch {
+                position,
+                typos: budget + 1,
+                exact: false,
+            };
+            if best.is_none() {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    best
+}
+
+/// Typo-tolerant, rank-ordered title search over `publications_fts`.
+///
+/// Unlike a raw FTS5 `MATCH`, this tolerates OCR noise, abbreviated venues
+/// and transposed words in the query: each query word is allowed 0–2 typos
+/// scaled by its length (see [`allowed_typos`]), and results are re-ranked
+/// by a fixed rule sequence — (1) number of query words matched, (2) total
+/// typos incurred, (3) term proximity in the stored title, (4) exactness
+/// (whole-word vs prefix) — rather than FTS5's opaque BM25 ordering.
+///
+/// Implementation: FTS5 has no native edit-distance operator, so we first
+/// run a broad OR-ed recall query (each query word, prefix-wildcarded) to
+/// pull in [`SEARCH_CANDIDATE_LIMIT`] candidates, then score and re-rank
+/// those candidates in Rust against the true per-word typo budget.
+///
+/// Returns up to `k` `(publication_id, confidence)` pairs, confidence
+/// normalized to `0.0..=1.0`, best match first.
+pub fn search_titles(
+    conn: &Connection,
+    query: &str,
+    k: usize,
+) -> Result<Vec<(i64, f64)>, DblpError> {
+    let query_words = tokenize(query);
+    if query_words.is_empty() || k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let fts_query = query_words
+        .iter()
+        .map(|w| format!("{}*", escape_fts_token(w)))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    let candidates = pull_candidates(conn, &fts_query)?;
+    Ok(rank_candidates(&query_words, candidates, k))
+}
+
+/// Near-duplicate title search via 64-bit SimHash fingerprints (see
+/// [`simhash64`]), for titles mangled badly enough (OCR noise, scanned PDF
+/// artifacts) that too few individual tokens survive for [`search_titles`]'s
+/// per-word edit-distance budget to find them.
+///
+/// Titles shorter than [`MIN_TOKENS_FOR_SIMHASH`] tokens fall back to
+/// [`search_titles`] outright — a fingerprint built from only a couple of
+/// tokens doesn't carry enough signal to rank reliably. An empty query
+/// always returns no results.
+///
+/// Like [`search_titles`], recalls candidates through an OR-ed FTS5 `MATCH`
+/// first (cheap, indexed) and only computes Hamming distance — `O(1)` per
+/// row, but still work to skip on rows that share no token at all — over
+/// that narrowed candidate set. Returns up to `k` `(publication_id,
+/// confidence)` pairs, best match first, where `confidence = 1.0 -
+/// distance / 64.0`.
+pub fn search_titles_by_simhash(
+    conn: &Connection,
+    query: &str,
+    k: usize,
+    max_distance: u32,
+) -> Result<Vec<(i64, f64)>, DblpError> {
+    let query_words = tokenize(query);
+    if query_words.is_empty() || k == 0 {
+        return Ok(Vec::new());
+    }
+    if query_words.len() < MIN_TOKENS_FOR_SIMHASH {
+        return search_titles(conn, query, k);
+    }
+
+    let query_fingerprint = simhash64(query);
+    let fts_query = query_words
+        .iter()
+        .map(|w| format!("{}*", escape_fts_token(w)))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    let mut scored: Vec<(i64, u32)> = pull_candidates_with_simhash(conn, &fts_query)?
+        .into_iter()
+        .filter_map(|(id, fingerprint)| {
+            let distance = hamming_distance(query_fingerprint, fingerprint);
+            (distance <= max_distance).then_some((id, distance))
+        })
+        .collect();
+    scored.sort_by_key(|&(_, distance)| distance);
+    scored.truncate(k);
+
+    Ok(scored
+        .into_iter()
+        .map(|(id, distance)| (id, 1.0 - (distance as f64 / 64.0)))
+        .collect())
+}
+
+/// Like [`pull_candidates`], but returns each row's SimHash fingerprint
+/// instead of its title, for [`search_titles_by_simhash`]. Skips rows
+/// without a fingerprint yet (shouldn't happen past schema version 4, since
+/// both [`insert_or_get_publication`] and the migration backfill set it).
+fn pull_candidates_with_simhash(
+    conn: &Connection,
+    fts_query: &str,
+) -> Result<Vec<(i64, i64)>, DblpError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT p.id, p.simhash FROM publications p \
+         WHERE p.id IN (SELECT rowid FROM publications_fts WHERE title MATCH ?1) \
+         AND p.simhash IS NOT NULL \
+         LIMIT ?2",
+    )?;
+    let candidates = stmt
+        .query_map(params![fts_query, SEARCH_CANDIDATE_LIMIT as i64], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(candidates)
+}
+
+/// Run an FTS5 `MATCH` expression and pull in up to [`SEARCH_CANDIDATE_LIMIT`]
+/// `(id, title)` rows for re-ranking. Shared by [`search_titles`] and the
+/// fuzzy-recall path in the `fuzzy` module, which builds a broader
+/// `fts_query` that also OR's in near-miss dictionary tokens.
+pub(crate) fn pull_candidates(
+    conn: &Connection,
+    fts_query: &str,
+) -> Result<Vec<(i64, String)>, DblpError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT p.id, p.title FROM publications p \
+         WHERE p.id IN (SELECT rowid FROM publications_fts WHERE title MATCH ?1) \
+         LIMIT ?2",
+    )?;
+    let candidates = stmt
+        .query_map(
+            params![fts_query, SEARCH_CANDIDATE_LIMIT as i64],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(candidates)
+}
+
+/// Score and re-rank `candidates` against `query_words` using the rule
+/// sequence described on [`search_titles`], returning the top `k`.
+pub(crate) fn rank_candidates(
+    query_words: &[String],
+    candidates: Vec<(i64, String)>,
+    k: usize,
+) -> Vec<(i64, f64)> {
+    struct Scored {
+        id: i64,
+        matched: usize,
+        total_typos: usize,
+        proximity: usize,
+        exact: usize,
+        confidence: f64,
+    }
+
+    let mut scored: Vec<Scored> = Vec::with_capacity(candidates.len());
+    for (id, title) in &candidates {
+        let title_tokens = tokenize(title);
+        let mut matched = 0usize;
+        let mut total_typos = 0usize;
+        let mut exact = 0usize;
+        let mut positions = Vec::with_capacity(query_words.len());
+
+        for word in query_words {
+            if let Some(m) = best_word_match(word, &title_tokens) {
+                matched += 1;
+                total_typos += m.typos;
+                positions.push(m.position);
+                if m.exact {
+                    exact += 1;
+                }
+            }
+        }
+
+        if matched == 0 {
+            continue;
+        }
+
+        let proximity = match (positions.iter().min(), positions.iter().max()) {
+            (Some(min), Some(max)) => max - min,
+            _ => 0,
+        };
+
+        let coverage = matched as f64 / query_words.len() as f64;
+        let typo_penalty = 1.0 - (total_typos as f64 / (matched as f64 * 3.0 + 1.0)).min(1.0);
+        let proximity_score = 1.0 / (1.0 + proximity as f64);
+        let exactness_score = exact as f64 / query_words.len() as f64;
+        let confidence =
+            (0.5 * coverage + 0.2 * typo_penalty + 0.15 * exactness_score + 0.15 * proximity_score)
+                .clamp(0.0, 1.0);
+
+        scored.push(Scored {
+            id: *id,
+            matched,
+            total_typos,
+            proximity,
+            exact,
+            confidence,
+        });
+    }
+
+    scored.sort_by(|a, b| {
+        b.matched
+            .cmp(&a.matched)
+            .then(a.total_typos.cmp(&b.total_typos))
+            .then(a.proximity.cmp(&b.proximity))
+            .then(b.exact.cmp(&a.exact))
+    });
+    scored.truncate(k);
+
+    scored.into_iter().map(|s| (s.id, s.confidence)).collect()
+}
+
 /// Initialize the database with the required schema.
 /// Sets WAL mode and NORMAL synchronous for performance.
 pub fn init_database(conn: &Connection) -> Result<(), DblpError> {
@@ -17,13 +399,17 @@ pub fn init_database(conn: &Connection) -> Result<(), DblpError> {
         r#"
         CREATE TABLE IF NOT EXISTS authors (
             id INTEGER PRIMARY KEY,
-            name TEXT UNIQUE NOT NULL
+            name TEXT UNIQUE NOT NULL,
+            key TEXT
         );
 
+        CREATE UNIQUE INDEX IF NOT EXISTS authors_key_idx ON authors(key) WHERE key IS NOT NULL;
+
         CREATE TABLE IF NOT EXISTS publications (
             id INTEGER PRIMARY KEY,
             key TEXT UNIQUE NOT NULL,
-            title TEXT NOT NULL
+            title TEXT NOT NULL,
+            simhash INTEGER
         );
 
         CREATE TABLE IF NOT EXISTS publication_authors (
@@ -48,6 +434,177 @@ pub fn init_database(conn: &Connection) -> Result<(), DblpError> {
     Ok(())
 }
 
+/// Like [`init_database`], but also creates a secondary
+/// `publications_trigram` FTS5 table (the built-in `trigram` tokenizer)
+/// over `title`. Querying it lets a title mangled by LaTeX escape artifacts
+/// (`\"o`, `{}`, `$...$` splitting or corrupting tokens so the primary
+/// token index never lines up) still be recovered by overlapping character
+/// 3-grams, at the cost of roughly doubling on-disk index size — opt into
+/// this only when that fallback is actually needed.
+pub fn init_database_with_trigram(conn: &Connection) -> Result<(), DblpError> {
+    init_database(conn)?;
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS publications_trigram USING fts5(
+            title,
+            content='publications',
+            content_rowid='id',
+            tokenize='trigram'
+        );",
+    )?;
+    Ok(())
+}
+
+/// Rebuild the companion trigram index created by
+/// [`init_database_with_trigram`]. Pairs with [`rebuild_fts_index`] for the
+/// primary token index — run both after a bulk load.
+pub fn rebuild_trigram_index(conn: &Connection) -> Result<(), DblpError> {
+    conn.execute(
+        "INSERT INTO publications_trigram(publications_trigram) VALUES('rebuild')",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Current on-disk schema version produced by [`migrate`]. Bump this, and
+/// add a step to [`MIGRATIONS`], whenever [`init_database`]'s schema changes
+/// in a way that requires upgrading an existing file rather than just
+/// creating a fresh one (e.g. adding a `year` column or an authors-FTS
+/// table).
+pub const CURRENT_SCHEMA_VERSION: i64 = 5;
+
+/// Migration step for schema version 4: adds the `publications.simhash`
+/// column (already present via [`init_database`] on a fresh database, so
+/// this checks first) and backfills it for every pre-existing row, so
+/// [`search_titles_by_simhash`] never has to special-case `NULL`
+/// fingerprints left over from before this column existed.
+fn migrate_add_simhash(tx: &Transaction) -> Result<(), DblpError> {
+    let has_column: bool = tx
+        .prepare("SELECT 1 FROM pragma_table_info('publications') WHERE name = 'simhash'")?
+        .exists([])?;
+    if !has_column {
+        tx.execute("ALTER TABLE publications ADD COLUMN simhash INTEGER", [])?;
+    }
+
+    let rows: Vec<(i64, String)> = {
+        let mut stmt =
+            tx.prepare("SELECT id, title FROM publications WHERE simhash IS NULL")?;
+        stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect()
+    };
+
+    let mut update = tx.prepare("UPDATE publications SET simhash = ?1 WHERE id = ?2")?;
+    for (id, title) in rows {
+        update.execute(params![simhash64(&title), id])?;
+    }
+    Ok(())
+}
+
+/// Migration step for schema version 5: adds the `authors.key` column
+/// (already present via [`init_database`] on a fresh database, so this
+/// checks first), plus its unique partial index — lets the bulk-build
+/// pipeline ([`insert_batch`]) resolve an author referenced by RDF URI in
+/// one batch against a name triple for that same URI inserted by a
+/// different worker's batch, by key instead of by name.
+fn migrate_add_author_key(tx: &Transaction) -> Result<(), DblpError> {
+    let has_table: bool = tx
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'authors'")?
+        .exists([])?;
+    if !has_table {
+        // Nothing to migrate yet; `init_database` creates `authors` with the
+        // `key` column already present for a fresh database.
+        return Ok(());
+    }
+
+    let has_column: bool = tx
+        .prepare("SELECT 1 FROM pragma_table_info('authors') WHERE name = 'key'")?
+        .exists([])?;
+    if !has_column {
+        tx.execute("ALTER TABLE authors ADD COLUMN key TEXT", [])?;
+    }
+    tx.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS authors_key_idx ON authors(key) WHERE key IS NOT NULL",
+        [],
+    )?;
+    Ok(())
+}
+
+/// One migration step, bringing a database from the version immediately
+/// below `target_version` up to `target_version`.
+struct Migration {
+    target_version: i64,
+    apply: fn(&Transaction) -> Result<(), DblpError>,
+}
+
+/// Ordered migration steps applied in sequence by [`migrate`]. Versions 1
+/// and 2 predate this subsystem and never changed the schema
+/// [`init_database`] creates, so they're recorded here as no-ops purely so
+/// [`migrate`] has a complete version history to walk an old database
+/// through.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        target_version: 1,
+        apply: |_tx| Ok(()),
+    },
+    Migration {
+        target_version: 2,
+        apply: |_tx| Ok(()),
+    },
+    Migration {
+        target_version: 3,
+        apply: |_tx| Ok(()),
+    },
+    Migration {
+        target_version: 4,
+        apply: migrate_add_simhash,
+    },
+    Migration {
+        target_version: 5,
+        apply: migrate_add_author_key,
+    },
+];
+
+/// Bring `conn`'s schema up to [`CURRENT_SCHEMA_VERSION`].
+///
+/// Reads the stored `schema_version` metadata (absent ⇒ a freshly created
+/// file, treated as version 0), then applies every [`MIGRATIONS`] step whose
+/// `target_version` exceeds it, in a single transaction, bumping the stored
+/// version after each step. Returns an error if the on-disk version is
+/// already newer than this binary's [`CURRENT_SCHEMA_VERSION`] — that
+/// database was written by a newer build, and downgrading it isn't
+/// supported.
+pub fn migrate(conn: &mut Connection) -> Result<(), DblpError> {
+    let current: i64 = get_metadata(conn, "schema_version")?
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    if current > CURRENT_SCHEMA_VERSION {
+        return Err(DblpError::Download(format!(
+            "database schema version {current} is newer than this build supports (max {CURRENT_SCHEMA_VERSION})"
+        )));
+    }
+
+    let pending: Vec<&Migration> = MIGRATIONS
+        .iter()
+        .filter(|m| m.target_version > current)
+        .collect();
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let tx = conn.transaction()?;
+    for migration in pending {
+        (migration.apply)(&tx)?;
+        tx.execute(
+            "INSERT INTO metadata (key, value) VALUES ('schema_version', ?1) \
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![migration.target_version.to_string()],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
 /// Configure pragmas for fast bulk loading.
 /// Uses `synchronous = OFF` to skip fsync on periodic commits â€” safe because a
 /// crashed build just needs to be re-run from scratch.
@@ -60,24 +617,85 @@ pub fn begin_bulk_load(conn: &Connection) -> Result<(), DblpError> {
     Ok(())
 }
 
-/// Batch of publication_author pairs for test helpers.
-#[cfg(test)]
+/// Batch of parsed records awaiting insertion, accumulated per-worker by
+/// [`crate::builder`]'s bulk-build pipeline and flushed to [`insert_batch`]
+/// once it reaches `BATCH_SIZE`.
+///
+/// `publications` and `authors` are raw `(key, text)` pairs straight off the
+/// RDF stream — `key` is the subject URI, not yet resolved to an integer id.
+/// `author_links` are raw `(publication key, author key)` authorship
+/// triples, also unresolved. `publication_authors` holds already-resolved
+/// `(pub_id, author_id)` pairs, for callers (including tests) that already
+/// have ids on hand and want to insert a relation directly.
 #[derive(Default)]
 pub struct InsertBatch {
-    pub publication_authors: Vec<(i64, i64)>, // (pub_id, author_id)
+    /// (publication key, title).
+    pub publications: Vec<(String, String)>,
+    /// (author key, name).
+    pub authors: Vec<(String, String)>,
+    /// (publication key, author key), resolved to ids during insertion.
+    pub author_links: Vec<(String, String)>,
+    /// Already-resolved (pub_id, author_id) pairs.
+    pub publication_authors: Vec<(i64, i64)>,
 }
 
-#[cfg(test)]
 impl InsertBatch {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Total number of records (of any kind) accumulated so far — used by
+    /// the bulk-build pipeline to decide when a batch is full enough to
+    /// flush.
+    pub fn len(&self) -> usize {
+        self.publications.len()
+            + self.authors.len()
+            + self.author_links.len()
+            + self.publication_authors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
-/// Insert a batch of publication_author pairs (test helper).
-#[cfg(test)]
+/// Insert a batch of parsed publications, authors, and their relations in a
+/// single transaction.
+///
+/// Publications and authors are upserted first, building a key → id map
+/// from entries in *this* batch; `author_links`/`publication_authors` are
+/// then resolved against that map, falling back to a database lookup by key
+/// for a publication or author that was upserted by an earlier batch (e.g.
+/// one flushed by a different worker thread). A link whose key still can't
+/// be resolved — the far side hasn't been seen by any batch yet — is
+/// dropped rather than failing the whole batch; in practice this doesn't
+/// happen because a record's title, authorship, and creator-name triples
+/// all appear close together in the dump.
 pub fn insert_batch(conn: &Connection, batch: &InsertBatch) -> Result<(), DblpError> {
     let tx = conn.unchecked_transaction()?;
+
+    let mut pub_ids: HashMap<&str, i64> = HashMap::with_capacity(batch.publications.len());
+    for (key, title) in &batch.publications {
+        let fingerprint = simhash64(title);
+        let mut stmt = tx.prepare_cached(
+            "INSERT INTO publications (key, title, simhash) VALUES (?1, ?2, ?3) \
+             ON CONFLICT(key) DO UPDATE SET title = excluded.title, simhash = excluded.simhash \
+             RETURNING id",
+        )?;
+        let id: i64 = stmt.query_row(params![key, title, fingerprint], |row| row.get(0))?;
+        pub_ids.insert(key.as_str(), id);
+    }
+
+    let mut author_ids: HashMap<&str, i64> = HashMap::with_capacity(batch.authors.len());
+    for (key, name) in &batch.authors {
+        let mut stmt = tx.prepare_cached(
+            "INSERT INTO authors (key, name) VALUES (?1, ?2) \
+             ON CONFLICT(key) DO UPDATE SET name = excluded.name RETURNING id",
+        )?;
+        let id: i64 = stmt.query_row(params![key, name], |row| row.get(0))?;
+        author_ids.insert(key.as_str(), id);
+    }
+
     {
         let mut rel_stmt = tx.prepare_cached(
             "INSERT OR IGNORE INTO publication_authors (pub_id, author_id) VALUES (?1, ?2)",
@@ -85,7 +703,29 @@ pub fn insert_batch(conn: &Connection, batch: &InsertBatch) -> Result<(), DblpEr
         for (pub_id, author_id) in &batch.publication_authors {
             rel_stmt.execute(params![pub_id, author_id])?;
         }
+
+        let mut pub_by_key =
+            tx.prepare_cached("SELECT id FROM publications WHERE key = ?1")?;
+        let mut author_by_key = tx.prepare_cached("SELECT id FROM authors WHERE key = ?1")?;
+        for (pub_key, author_key) in &batch.author_links {
+            let pub_id = match pub_ids.get(pub_key.as_str()) {
+                Some(&id) => Some(id),
+                None => pub_by_key
+                    .query_row(params![pub_key], |row| row.get(0))
+                    .optional()?,
+            };
+            let author_id = match author_ids.get(author_key.as_str()) {
+                Some(&id) => Some(id),
+                None => author_by_key
+                    .query_row(params![author_key], |row| row.get(0))
+                    .optional()?,
+            };
+            if let (Some(pub_id), Some(author_id)) = (pub_id, author_id) {
+                rel_stmt.execute(params![pub_id, author_id])?;
+            }
+        }
     }
+
     tx.commit()?;
     Ok(())
 }
@@ -103,16 +743,22 @@ pub fn insert_or_get_author(conn: &Connection, name: &str) -> Result<i64, DblpEr
 
 /// Insert or update a publication by key, returning the integer ID.
 /// Uses RETURNING clause for a single round-trip instead of INSERT + SELECT.
+///
+/// Also computes and stores the title's SimHash fingerprint (see
+/// [`simhash64`]), so [`search_titles_by_simhash`] never needs to
+/// backfill anything inserted after schema version 4.
 pub fn insert_or_get_publication(
     conn: &Connection,
     key: &str,
     title: &str,
 ) -> Result<i64, DblpError> {
+    let fingerprint = simhash64(title);
     let mut stmt = conn.prepare_cached(
-        "INSERT INTO publications (key, title) VALUES (?1, ?2) \
-         ON CONFLICT(key) DO UPDATE SET title = excluded.title RETURNING id",
+        "INSERT INTO publications (key, title, simhash) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET title = excluded.title, simhash = excluded.simhash \
+         RETURNING id",
     )?;
-    let id: i64 = stmt.query_row(params![key, title], |row| row.get(0))?;
+    let id: i64 = stmt.query_row(params![key, title, fingerprint], |row| row.get(0))?;
     Ok(id)
 }
 
@@ -131,6 +777,113 @@ pub fn vacuum(conn: &Connection) -> Result<(), DblpError> {
     Ok(())
 }
 
+/// Run SQLite's built-in query-planner optimizer. Cheap enough to run after
+/// every build — unlike [`analyze`], it only touches tables whose
+/// statistics look stale rather than rebuilding all of them.
+pub fn optimize(conn: &Connection) -> Result<(), DblpError> {
+    conn.execute_batch("PRAGMA optimize;")?;
+    Ok(())
+}
+
+/// Rebuild query-planner statistics for every table. Pairs with
+/// [`optimize`] at the end of a build, where the millions of rows written by
+/// [`insert_batch`] would otherwise leave stale statistics until SQLite
+/// happened to notice on its own.
+pub fn analyze(conn: &Connection) -> Result<(), DblpError> {
+    conn.execute_batch("ANALYZE;")?;
+    Ok(())
+}
+
+/// Progress events for [`run_maintenance`], mirroring `BuildProgress`'s
+/// shape for an on-demand maintenance pass rather than a full rebuild.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceProgress {
+    Optimizing,
+    Vacuuming,
+    Complete { elapsed_ms: u128 },
+}
+
+/// Run on-demand maintenance against an existing database file: always
+/// `PRAGMA optimize`, and — only when `run_vacuum` is set, since `VACUUM`
+/// rewrites the entire file and can take minutes on the full DBLP dump — an
+/// explicit `VACUUM`. Reports timing through `progress` so callers can show
+/// elapsed time for the slow path.
+pub fn run_maintenance(
+    conn: &Connection,
+    run_vacuum: bool,
+    mut progress: impl FnMut(MaintenanceProgress),
+) -> Result<(), DblpError> {
+    let start = std::time::Instant::now();
+
+    progress(MaintenanceProgress::Optimizing);
+    optimize(conn)?;
+
+    if run_vacuum {
+        progress(MaintenanceProgress::Vacuuming);
+        vacuum(conn)?;
+    }
+
+    progress(MaintenanceProgress::Complete {
+        elapsed_ms: start.elapsed().as_millis(),
+    });
+    Ok(())
+}
+
+/// Progress events for [`snapshot_to`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotProgress {
+    /// One backup step completed; `remaining`/`total` are SQLite page
+    /// counts, so callers can render a percentage.
+    Copying { remaining: i32, total: i32 },
+    RebuildingIndex,
+    Vacuuming,
+    Complete { elapsed_ms: u128 },
+}
+
+/// Materialize a live (possibly in-memory) connection as a compact,
+/// FTS-ready file at `path`.
+///
+/// Copies page-by-page via SQLite's Online Backup API, so `conn` can be an
+/// in-memory database built for speed and still be atomically turned into a
+/// distributable file without ever having been opened against disk itself.
+/// After the copy, rebuilds the destination's FTS5 index and `VACUUM`s it —
+/// the backup copies `conn`'s page layout verbatim, which may not reflect a
+/// rebuilt/optimized index if the source skipped those steps.
+pub fn snapshot_to(
+    conn: &Connection,
+    path: &Path,
+    mut progress: impl FnMut(SnapshotProgress),
+) -> Result<(), DblpError> {
+    let start = std::time::Instant::now();
+    let mut dst = Connection::open(path)?;
+
+    {
+        let backup = Backup::new(conn, &mut dst)?;
+        loop {
+            let step = backup.step(100)?;
+            let page_progress = backup.progress();
+            progress(SnapshotProgress::Copying {
+                remaining: page_progress.remaining,
+                total: page_progress.pagecount,
+            });
+            if step == StepResult::Done {
+                break;
+            }
+        }
+    }
+
+    progress(SnapshotProgress::RebuildingIndex);
+    rebuild_fts_index(&dst)?;
+
+    progress(SnapshotProgress::Vacuuming);
+    vacuum(&dst)?;
+
+    progress(SnapshotProgress::Complete {
+        elapsed_ms: start.elapsed().as_millis(),
+    });
+    Ok(())
+}
+
 /// Get a metadata value by key.
 pub fn get_metadata(conn: &Connection, key: &str) -> Result<Option<String>, DblpError> {
     let mut stmt = conn.prepare_cached("SELECT value FROM metadata WHERE key = ?1")?;
@@ -260,6 +1013,88 @@ mod tests {
         assert_eq!(authors, vec!["Alice", "Bob"]);
     }
 
+    #[test]
+    fn test_insert_batch_resolves_raw_keys_in_one_batch() {
+        let conn = setup_db();
+
+        let mut batch = InsertBatch::new();
+        batch
+            .publications
+            .push(("rec/1".to_string(), "Paper".to_string()));
+        batch
+            .authors
+            .push(("person/alice".to_string(), "Alice".to_string()));
+        batch
+            .author_links
+            .push(("rec/1".to_string(), "person/alice".to_string()));
+        insert_batch(&conn, &batch).unwrap();
+
+        let pub_id = insert_or_get_publication(&conn, "rec/1", "Paper").unwrap();
+        assert_eq!(
+            get_authors_for_publication(&conn, pub_id).unwrap(),
+            vec!["Alice".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_insert_batch_resolves_author_link_against_earlier_batch() {
+        let conn = setup_db();
+
+        // The publication and author are inserted in one batch (simulating
+        // one worker's chunk)...
+        let mut first = InsertBatch::new();
+        first
+            .publications
+            .push(("rec/1".to_string(), "Paper".to_string()));
+        first
+            .authors
+            .push(("person/alice".to_string(), "Alice".to_string()));
+        insert_batch(&conn, &first).unwrap();
+
+        // ...and the authorship link arrives in a later batch (simulating a
+        // different worker's chunk flushing afterwards), referencing both
+        // keys only by their raw RDF URIs.
+        let mut second = InsertBatch::new();
+        second
+            .author_links
+            .push(("rec/1".to_string(), "person/alice".to_string()));
+        insert_batch(&conn, &second).unwrap();
+
+        let pub_id = insert_or_get_publication(&conn, "rec/1", "Paper").unwrap();
+        assert_eq!(
+            get_authors_for_publication(&conn, pub_id).unwrap(),
+            vec!["Alice".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_insert_batch_drops_link_with_unresolvable_key() {
+        let conn = setup_db();
+
+        let mut batch = InsertBatch::new();
+        batch
+            .author_links
+            .push(("rec/missing".to_string(), "person/missing".to_string()));
+        // Should not error even though neither key resolves to anything.
+        insert_batch(&conn, &batch).unwrap();
+
+        let (_, _, rels) = get_counts(&conn).unwrap();
+        assert_eq!(rels, 0);
+    }
+
+    #[test]
+    fn test_insert_batch_len_and_is_empty() {
+        let mut batch = InsertBatch::new();
+        assert!(batch.is_empty());
+        assert_eq!(batch.len(), 0);
+
+        batch
+            .publications
+            .push(("rec/1".to_string(), "Paper".to_string()));
+        assert!(!batch.is_empty());
+        assert_eq!(batch.len(), 1);
+    }
+
     #[test]
     fn test_insert_or_get_author_deduplicates() {
         let conn = setup_db();
@@ -296,4 +1131,298 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].0, "rec/1");
     }
+
+    #[test]
+    fn test_search_titles_exact_match_ranks_first() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        insert_or_get_publication(&conn, "rec/2", "BERT Pre-training of Deep Bidirectional Transformers").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles(&conn, "Attention Is All You Need", 5).unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0].0, 1);
+        assert!(results[0].1 > 0.9, "expected high confidence, got {}", results[0].1);
+    }
+
+    #[test]
+    fn test_search_titles_tolerates_typos() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        // "Attentoin" is a 1-typo corruption of "Attention".
+        let results = search_titles(&conn, "Attentoin Is All You Need", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 1);
+    }
+
+    #[test]
+    fn test_search_titles_ranks_more_matched_words_first() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Deep Residual Learning for Image Recognition").unwrap();
+        insert_or_get_publication(&conn, "rec/2", "Deep Learning").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles(&conn, "Deep Residual Learning for Image Recognition", 5).unwrap();
+        assert_eq!(results[0].0, 1);
+    }
+
+    #[test]
+    fn test_search_titles_no_match_returns_empty() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles(&conn, "Completely Unrelated Zzyzx Query", 5).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_titles_respects_k() {
+        let conn = setup_db();
+        for i in 0..10 {
+            insert_or_get_publication(&conn, &format!("rec/{i}"), "Neural Network Training Methods")
+                .unwrap();
+        }
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles(&conn, "Neural Network Training Methods", 3).unwrap();
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn test_migrate_fresh_database_reaches_current_version() {
+        let mut conn = setup_db();
+        migrate(&mut conn).unwrap();
+        assert_eq!(
+            get_metadata(&conn, "schema_version").unwrap(),
+            Some(CURRENT_SCHEMA_VERSION.to_string())
+        );
+    }
+
+    #[test]
+    fn test_migrate_is_idempotent() {
+        let mut conn = setup_db();
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(
+            get_metadata(&conn, "schema_version").unwrap(),
+            Some(CURRENT_SCHEMA_VERSION.to_string())
+        );
+    }
+
+    #[test]
+    fn test_migrate_applies_only_pending_steps() {
+        let mut conn = setup_db();
+        set_metadata(&conn, "schema_version", "1").unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(
+            get_metadata(&conn, "schema_version").unwrap(),
+            Some(CURRENT_SCHEMA_VERSION.to_string())
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_on_disk_version() {
+        let mut conn = setup_db();
+        set_metadata(&conn, "schema_version", &(CURRENT_SCHEMA_VERSION + 1).to_string()).unwrap();
+        assert!(migrate(&mut conn).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_to_materializes_fts_ready_file() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_path = dir.path().join("snapshot.db");
+
+        let mut events = Vec::new();
+        snapshot_to(&conn, &snapshot_path, |p| events.push(p)).unwrap();
+
+        assert!(matches!(events.last(), Some(SnapshotProgress::Complete { .. })));
+
+        let snapshot = Connection::open(&snapshot_path).unwrap();
+        let results = search_titles(&snapshot, "Attention Is All You Need", 5).unwrap();
+        assert_eq!(results[0].0, 1);
+    }
+
+    #[test]
+    fn test_trigram_index_recovers_substring_match() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_database_with_trigram(&conn).unwrap();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+        rebuild_trigram_index(&conn).unwrap();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT p.key FROM publications p \
+                 WHERE p.id IN (SELECT rowid FROM publications_trigram WHERE publications_trigram MATCH ?1)",
+            )
+            .unwrap();
+        let results: Vec<String> = stmt
+            .query_map(params!["ttention is"], |row| row.get(0))
+            .unwrap()
+            .filter_map(|r| r.ok())
+            .collect();
+
+        assert_eq!(results, vec!["rec/1".to_string()]);
+    }
+
+    #[test]
+    fn test_levenshtein_basic() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+        assert_eq!(levenshtein("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_simhash64_identical_titles_have_zero_distance() {
+        let a = simhash64("Attention Is All You Need");
+        let b = simhash64("Attention Is All You Need");
+        assert_eq!(hamming_distance(a, b), 0);
+    }
+
+    #[test]
+    fn test_simhash64_is_case_insensitive() {
+        let a = simhash64("Attention Is All You Need");
+        let b = simhash64("attention is all you need");
+        assert_eq!(hamming_distance(a, b), 0);
+    }
+
+    #[test]
+    fn test_simhash64_near_duplicate_titles_are_close() {
+        let a = simhash64("Deep Residual Learning for Image Recognition");
+        // OCR-style corruption of one word.
+        let b = simhash64("Deep Resldual Learning for Image Recognition");
+        assert!(
+            hamming_distance(a, b) <= 3,
+            "expected near-duplicate titles to have a small Hamming distance"
+        );
+    }
+
+    #[test]
+    fn test_simhash64_unrelated_titles_are_far_apart() {
+        let a = simhash64("Attention Is All You Need");
+        let b = simhash64("Convolutional Neural Networks for Sentence Classification");
+        assert!(hamming_distance(a, b) > 3);
+    }
+
+    #[test]
+    fn test_simhash64_empty_title_is_zero() {
+        assert_eq!(simhash64(""), 0);
+        assert_eq!(simhash64("   "), 0);
+    }
+
+    #[test]
+    fn test_insert_or_get_publication_stores_simhash() {
+        let conn = setup_db();
+        let pub_id = insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        let stored: i64 = conn
+            .query_row(
+                "SELECT simhash FROM publications WHERE id = ?1",
+                params![pub_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(stored, simhash64("Attention Is All You Need"));
+    }
+
+    #[test]
+    fn test_search_titles_by_simhash_finds_ocr_mangled_title() {
+        let conn = setup_db();
+        insert_or_get_publication(
+            &conn,
+            "rec/1",
+            "Deep Residual Learning for Image Recognition",
+        )
+        .unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles_by_simhash(
+            &conn,
+            "Deep Resldual Leaming for lmage Recognition",
+            5,
+            DEFAULT_SIMHASH_MAX_DISTANCE,
+        )
+        .unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results[0].0, 1);
+    }
+
+    #[test]
+    fn test_search_titles_by_simhash_rejects_distant_matches() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles_by_simhash(
+            &conn,
+            "Completely Unrelated Zzyzx Query About Something Else",
+            5,
+            DEFAULT_SIMHASH_MAX_DISTANCE,
+        )
+        .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_search_titles_by_simhash_falls_back_to_search_titles_for_short_queries() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Deep Learning").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        // Fewer than MIN_TOKENS_FOR_SIMHASH tokens: exact/typo-tolerant path.
+        let results =
+            search_titles_by_simhash(&conn, "Deep Learning", 5, DEFAULT_SIMHASH_MAX_DISTANCE)
+                .unwrap();
+        assert_eq!(results[0].0, 1);
+    }
+
+    #[test]
+    fn test_search_titles_by_simhash_empty_query_returns_empty() {
+        let conn = setup_db();
+        insert_or_get_publication(&conn, "rec/1", "Attention Is All You Need").unwrap();
+        rebuild_fts_index(&conn).unwrap();
+
+        let results = search_titles_by_simhash(&conn, "", 5, DEFAULT_SIMHASH_MAX_DISTANCE).unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_backfills_simhash_for_legacy_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Simulate a pre-simhash database: old schema, no simhash column.
+        conn.execute_batch(
+            "CREATE TABLE publications (
+                id INTEGER PRIMARY KEY,
+                key TEXT UNIQUE NOT NULL,
+                title TEXT NOT NULL
+             );
+             CREATE TABLE metadata (
+                key TEXT PRIMARY KEY,
+                value TEXT
+             );",
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO publications (key, title) VALUES ('rec/1', 'Attention Is All You Need')",
+            [],
+        )
+        .unwrap();
+        set_metadata(&conn, "schema_version", "3").unwrap();
+
+        let mut conn = conn;
+        migrate(&mut conn).unwrap();
+
+        let stored: i64 = conn
+            .query_row("SELECT simhash FROM publications WHERE key = 'rec/1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(stored, simhash64("Attention Is All You Need"));
+    }
 }
@@ -0,0 +1,135 @@
+//! Typo-tolerant candidate recall for [`crate::db::search_titles`].
+//!
+//! `search_titles` already re-ranks candidates with a per-word edit-distance
+//! budget, but it can only rank what the initial FTS5 `MATCH` pulled in, and
+//! that pull is a prefix-wildcarded exact-token query — a typo in the first
+//! few characters of a word (e.g. "Qttention" for "Attention") never reaches
+//! the candidate set at all. [`TokenFst`] closes that gap: it indexes every
+//! distinct token seen in `publications.title` into a finite-state
+//! transducer, and [`search_titles_fuzzy`] intersects a per-token Levenshtein
+//! automaton with that FST to recall near-miss tokens before handing the
+//! broadened `MATCH` expression to the same candidate-pull/ranking path.
+
+use std::collections::BTreeSet;
+
+use fst::automaton::Levenshtein;
+use fst::{IntoStreamer, Set, Streamer};
+use rusqlite::Connection;
+
+use crate::db;
+use crate::DblpError;
+
+/// Maximum number of fuzzy variants OR'd into the `MATCH` expression per
+/// query token, kept small so the expanded query stays bounded even for
+/// tokens with many near-miss dictionary entries.
+const MAX_VARIANTS_PER_TOKEN: usize = 16;
+
+/// Maximum edit distance to tolerate for a token, scaled by its length: short
+/// tokens are only a typo away from meaning something else entirely, so we
+/// require an exact match for them.
+fn max_edit_distance(token: &str) -> u32 {
+    match token.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// A finite-state transducer over every distinct title token, used to
+/// recall near-miss spellings at query time.
+///
+/// Built from the same [`db::tokenize`] pipeline used at insert time, so a
+/// query token's fuzzy variants are always drawn from the same normalization
+/// as the indexed titles.
+pub struct TokenFst {
+    set: Set<Vec<u8>>,
+}
+
+impl TokenFst {
+    /// Build a [`TokenFst`] over every distinct token in `publications.title`.
+    /// Intended to be rebuilt after [`db::rebuild_fts_index`] and reused
+    /// across queries rather than rebuilt per call.
+    pub fn build(conn: &Connection) -> Result<Self, DblpError> {
+        let mut stmt = conn.prepare("SELECT title FROM publications")?;
+        let mut tokens: BTreeSet<String> = BTreeSet::new();
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        for row in rows {
+            tokens.extend(db::tokenize(&row?));
+        }
+
+        let set = Set::from_iter(tokens)
+            .expect("tokens are deduplicated and lexicographically sorted by BTreeSet");
+        Ok(Self { set })
+    }
+
+    /// Dictionary tokens within `token`'s edit-distance budget, nearest
+    /// first and capped at [`MAX_VARIANTS_PER_TOKEN`]. Empty if the budget is
+    /// zero (short tokens) or the token has no close neighbors.
+    fn variants(&self, token: &str) -> Vec<String> {
+        let max_dist = max_edit_distance(token);
+        if max_dist == 0 {
+            return Vec::new();
+        }
+
+        let automaton = match Levenshtein::new(token, max_dist) {
+            Ok(automaton) => automaton,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut stream = self.set.search(automaton).into_stream();
+        let mut found: Vec<(usize, String)> = Vec::new();
+        while let Some(key) = stream.next() {
+            let Ok(word) = std::str::from_utf8(key) else {
+                continue;
+            };
+            if word == token {
+                continue;
+            }
+            found.push((db::levenshtein(token, word), word.to_string()));
+        }
+
+        found.sort_by_key(|(dist, _)| *dist);
+        found.truncate(MAX_VARIANTS_PER_TOKEN);
+        found.into_iter().map(|(_, word)| word).collect()
+    }
+}
+
+/// Build an FTS5 `MATCH` expression that OR's each query token (prefix
+/// wildcarded, as in [`db::search_titles`]) with its fuzzy variants from
+/// `fst`.
+fn build_fuzzy_match_query(query_words: &[String], fst: &TokenFst) -> String {
+    query_words
+        .iter()
+        .map(|word| {
+            let mut alternatives = vec![format!("{}*", db::escape_fts_token(word))];
+            alternatives.extend(
+                fst.variants(word)
+                    .iter()
+                    .map(|variant| db::escape_fts_token(variant)),
+            );
+            format!("({})", alternatives.join(" OR "))
+        })
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+/// Typo-tolerant title search, like [`db::search_titles`], but with fuzzy
+/// candidate recall: query tokens that don't prefix-match any indexed title
+/// still pull in candidates via `fst`'s near-miss tokens before the same
+/// edit-distance re-ranking is applied. Opt-in — callers that only need
+/// exact-token recall should keep using [`db::search_titles`].
+pub fn search_titles_fuzzy(
+    conn: &Connection,
+    query: &str,
+    k: usize,
+    fst: &TokenFst,
+) -> Result<Vec<(i64, f64)>, DblpError> {
+    let query_words = db::tokenize(query);
+    if query_words.is_empty() || k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let fts_query = build_fuzzy_match_query(&query_words, fst);
+    let candidates = db::pull_candidates(conn, &fts_query)?;
+    Ok(db::rank_candidates(&query_words, candidates, k))
+}
@@ -0,0 +1,237 @@
+//! Query-time title lookup: normalizes a caller-supplied title, runs it
+//! through [`db::search_titles`] (or the fuzzy-recall path in [`crate::fuzzy`]),
+//! and reports the best match above a confidence threshold, if any.
+
+use std::collections::HashSet;
+
+use rusqlite::{params, Connection};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::db;
+use crate::fuzzy::TokenFst;
+use crate::DblpError;
+
+/// Confidence below which [`query_fts`] reports no match rather than a
+/// low-quality guess, chosen to sit below the score of a genuine single-typo
+/// hit but above the noise floor of an unrelated title sharing a few words.
+pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// A DBLP publication as returned by [`query_fts`].
+#[derive(Debug, Clone)]
+pub struct PublicationRecord {
+    pub id: i64,
+    pub title: String,
+}
+
+/// A [`query_fts`] hit: the matched publication plus the confidence
+/// [`db::search_titles`] assigned it.
+#[derive(Debug, Clone)]
+pub struct QueryMatch {
+    pub record: PublicationRecord,
+    pub score: f64,
+}
+
+/// Tokenize `title` the same way the database does, for callers that want to
+/// inspect or display the words a query will actually search on.
+pub fn get_query_words(title: &str) -> Vec<String> {
+    db::tokenize(title)
+}
+
+/// Normalize a title for equality comparisons (e.g. "did we match the right
+/// title") independent of punctuation and casing differences between a
+/// bibtex title and its DBLP counterpart.
+pub fn normalize_title(title: &str) -> String {
+    db::tokenize(title).join(" ")
+}
+
+fn fetch_match(conn: &Connection, id: i64, score: f64) -> Result<QueryMatch, DblpError> {
+    let title: String = conn.query_row(
+        "SELECT title FROM publications WHERE id = ?1",
+        params![id],
+        |row| row.get(0),
+    )?;
+    Ok(QueryMatch {
+        record: PublicationRecord { id, title },
+        score,
+    })
+}
+
+fn best_match(
+    conn: &Connection,
+    results: Vec<(i64, f64)>,
+    threshold: f64,
+) -> Result<Option<QueryMatch>, DblpError> {
+    let Some((id, score)) = results.into_iter().next() else {
+        return Ok(None);
+    };
+    if score < threshold {
+        return Ok(None);
+    }
+    fetch_match(conn, id, score).map(Some)
+}
+
+/// Look up the best-matching publication for `title`, or `None` if nothing
+/// scores at least `threshold`. Uses exact-token FTS5 recall; see
+/// [`query_fts_with_fuzzy`] for typo-tolerant recall.
+pub fn query_fts(
+    conn: &Connection,
+    title: &str,
+    threshold: f64,
+) -> Result<Option<QueryMatch>, DblpError> {
+    let results = db::search_titles(conn, title, 1)?;
+    best_match(conn, results, threshold)
+}
+
+/// Like [`query_fts`], but recalls candidates through `fst`'s fuzzy-token
+/// index first, so a typo anywhere in `title` — not just after the matching
+/// prefix — can still surface the right publication.
+pub fn query_fts_with_fuzzy(
+    conn: &Connection,
+    title: &str,
+    threshold: f64,
+    fst: &TokenFst,
+) -> Result<Option<QueryMatch>, DblpError> {
+    let results = crate::fuzzy::search_titles_fuzzy(conn, title, 1, fst)?;
+    best_match(conn, results, threshold)
+}
+
+/// Default weight given to author-set overlap when blending with the title
+/// score in [`query_fts_with_authors`]; the remainder stays on the title
+/// score.
+pub const DEFAULT_AUTHOR_WEIGHT: f64 = 0.3;
+
+/// Number of top FTS candidates considered for author-aware re-ranking —
+/// wider than [`query_fts`]'s top-1 lookup, since the right title can sit
+/// just behind a wrong one on title score alone.
+const AUTHOR_RERANK_CANDIDATES: usize = 10;
+
+/// Case/diacritic-folded surname: lowercase, diacritics stripped, keeping
+/// only the last whitespace-separated component of the full name. DBLP and
+/// bibtex author lists format names inconsistently (initials, given-name
+/// order), but usually agree on the surname.
+fn folded_surname(name: &str) -> String {
+    let surname = name.split_whitespace().next_back().unwrap_or(name);
+    surname
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+fn folded_surname_set(authors: &[String]) -> HashSet<String> {
+    authors.iter().map(|a| folded_surname(a)).collect()
+}
+
+/// Jaccard similarity between two surname sets; `0.0` if either is empty.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    intersection as f64 / union as f64
+}
+
+/// Like [`query_fts`], but re-ranks the top candidates by blending the title
+/// score with author-set overlap against `expected_authors` — surnames are
+/// case/diacritic-folded and compared via Jaccard similarity over
+/// [`db::get_authors_for_publication`]. This directly targets "confidently
+/// wrong" matches: when two candidates have near-equal title scores, the one
+/// whose authors actually overlap `expected_authors` wins.
+///
+/// `author_weight` controls how much author overlap can move a candidate's
+/// rank relative to its title score (`0.0` ⇒ pure title scoring; see
+/// [`DEFAULT_AUTHOR_WEIGHT`] for a reasonable default). Degrades to
+/// [`query_fts`] when `expected_authors` is empty, since there's nothing to
+/// blend against.
+pub fn query_fts_with_authors(
+    conn: &Connection,
+    title: &str,
+    expected_authors: &[String],
+    threshold: f64,
+    author_weight: f64,
+) -> Result<Option<QueryMatch>, DblpError> {
+    if expected_authors.is_empty() {
+        return query_fts(conn, title, threshold);
+    }
+
+    let candidates = db::search_titles(conn, title, AUTHOR_RERANK_CANDIDATES)?;
+    let expected = folded_surname_set(expected_authors);
+
+    let mut best: Option<(i64, f64)> = None;
+    for (id, title_score) in candidates {
+        let authors = db::get_authors_for_publication(conn, id)?;
+        let overlap = jaccard(&expected, &folded_surname_set(&authors));
+        let blended = (1.0 - author_weight) * title_score + author_weight * overlap;
+
+        if best.as_ref().is_none_or(|(_, b)| blended > *b) {
+            best = Some((id, blended));
+        }
+    }
+
+    match best {
+        Some((id, blended)) if blended >= threshold => fetch_match(conn, id, blended).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Recall strategy for [`query_fts_with_mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryMode {
+    /// Token-based FTS5 `MATCH` only — matches [`query_fts`]'s behavior.
+    #[default]
+    Exact,
+    /// Falls back to a substring query against `publications_trigram` (see
+    /// [`db::init_database_with_trigram`]) when the exact-token path scores
+    /// below threshold.
+    TrigramFallback,
+}
+
+/// Candidate rows pulled from a trigram-table substring match, bounded the
+/// same way [`db::search_titles`] bounds its token-match pull.
+const TRIGRAM_CANDIDATE_LIMIT: i64 = 500;
+
+/// Shortest normalized query the trigram tokenizer can usefully match on;
+/// anything shorter risks matching almost every title in the index.
+const MIN_TRIGRAM_QUERY_LEN: usize = 3;
+
+/// Like [`query_fts`], but when `mode` is [`QueryMode::TrigramFallback`] and
+/// the exact-token path scores below `threshold`, retries as a substring
+/// query against the `publications_trigram` table created by
+/// [`db::init_database_with_trigram`]. Substring/trigram recall survives
+/// LaTeX escape artifacts (`\"o`, `{}`, `$...$`) that split or corrupt the
+/// tokens the primary index relies on, at the cost of a second (pricier)
+/// query — callers that don't need it should stick with [`query_fts`].
+pub fn query_fts_with_mode(
+    conn: &Connection,
+    title: &str,
+    threshold: f64,
+    mode: QueryMode,
+) -> Result<Option<QueryMatch>, DblpError> {
+    let primary = query_fts(conn, title, threshold)?;
+    if primary.is_some() || mode == QueryMode::Exact {
+        return Ok(primary);
+    }
+
+    let normalized = normalize_title(title);
+    if normalized.chars().count() < MIN_TRIGRAM_QUERY_LEN {
+        return Ok(None);
+    }
+
+    let mut stmt = conn.prepare_cached(
+        "SELECT p.id, p.title FROM publications p \
+         WHERE p.id IN (SELECT rowid FROM publications_trigram WHERE publications_trigram MATCH ?1) \
+         LIMIT ?2",
+    )?;
+    let candidates: Vec<(i64, String)> = stmt
+        .query_map(
+            params![db::escape_fts_token(&normalized), TRIGRAM_CANDIDATE_LIMIT],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let query_words = get_query_words(title);
+    let ranked = db::rank_candidates(&query_words, candidates, 1);
+    best_match(conn, ranked, threshold)
+}
@@ -1,12 +1,17 @@
 //! S3 download + JSON parsing + Tantivy indexing for OpenAlex works.
 
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 
 use flate2::read::GzDecoder;
 use tantivy::doc;
 use tantivy::schema::*;
 use tantivy::{Index, IndexWriter};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
 
 use crate::metadata::{self, IndexMetadata};
 use crate::s3;
@@ -21,6 +26,112 @@ const ALLOWED_TYPES: &[&str] = &[
     "dissertation",
 ];
 
+/// Heap tantivy's `IndexWriter` is given, split across `writer_with_num_threads`
+/// indexing threads. Also the basis for [`batch_doc_count`], so a bigger
+/// heap (or fewer threads) means fewer, larger commits.
+const WRITER_HEAP_BYTES: usize = 256_000_000;
+
+/// Rough amortized on-disk size of one indexed document (title + authors +
+/// id), used only to turn a per-thread byte budget into a document count —
+/// doesn't need to be exact, just in the right order of magnitude.
+const ESTIMATED_DOC_BYTES: usize = 512;
+
+/// A downloaded-but-not-yet-parsed partition file, handed from the download
+/// stage to the parse/index stage.
+struct DownloadedFile {
+    date: String,
+    bytes: Vec<u8>,
+}
+
+/// The tantivy field handles every parse worker needs, bundled so they
+/// don't have to re-resolve them from the schema per call.
+#[derive(Clone, Copy)]
+struct IndexFields {
+    title: Field,
+    authors: Field,
+    institutions: Field,
+    abstract_text: Field,
+    id: Field,
+}
+
+/// Per-thread document count to accumulate before triggering a commit,
+/// sized milli-style: the writer's heap budget divided across its indexing
+/// threads, then converted to a document count via [`ESTIMATED_DOC_BYTES`].
+/// Floored so a very large thread count never drives it to zero.
+fn batch_doc_count(num_threads: usize) -> u64 {
+    let heap_per_thread = WRITER_HEAP_BYTES / num_threads.max(1);
+    ((heap_per_thread / ESTIMATED_DOC_BYTES).max(1_000)) as u64
+}
+
+/// Commits the writer and zeroes the uncommitted-doc counter. Shared by the
+/// doc-count batch threshold and per-partition checkpointing so both paths
+/// agree on what "already committed" means.
+fn commit_writer(writer: &RwLock<IndexWriter>, uncommitted: &AtomicU64) -> Result<(), OpenAlexError> {
+    writer
+        .write()
+        .unwrap()
+        .commit()
+        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
+    uncommitted.store(0, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Given the full set of partition dates processed by this run (in
+/// ascending order) and the set completed so far, returns the latest date
+/// such that every partition at or before it is complete — i.e. the
+/// furthest point a restart could safely resume from.
+fn contiguous_checkpoint(ordered_dates: &[String], completed: &HashSet<String>) -> Option<String> {
+    ordered_dates
+        .iter()
+        .take_while(|d| completed.contains(d.as_str()))
+        .next_back()
+        .cloned()
+}
+
+/// Marks `finished_date`'s partition complete and, if that advances the
+/// contiguous checkpoint, commits the writer and persists the checkpoint as
+/// `last_sync_date` so an interrupted build can resume from it instead of
+/// re-downloading everything. Commit happens before the metadata write so a
+/// crash between the two can never leave a checkpoint claiming documents
+/// that aren't actually durable — the existing delete-term upsert makes
+/// re-processing a not-yet-checkpointed partition safe either way.
+#[allow(clippy::too_many_arguments)]
+fn checkpoint_partition(
+    db_path: &Path,
+    ordered_dates: &[String],
+    completed: &Mutex<HashSet<String>>,
+    finished_date: &str,
+    writer: &RwLock<IndexWriter>,
+    uncommitted: &AtomicU64,
+    total_records: &AtomicU64,
+    base_publication_count: u64,
+) -> Result<(), OpenAlexError> {
+    let checkpoint_date = {
+        let mut completed = completed.lock().unwrap();
+        completed.insert(finished_date.to_string());
+        contiguous_checkpoint(ordered_dates, &completed)
+    };
+    let Some(checkpoint_date) = checkpoint_date else {
+        return Ok(());
+    };
+
+    commit_writer(writer, uncommitted)?;
+
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    metadata::write_metadata(
+        db_path,
+        &IndexMetadata {
+            schema_version: CURRENT_SCHEMA_VERSION.to_string(),
+            build_date: Some(now.to_string()),
+            publication_count: Some(base_publication_count + total_records.load(Ordering::Relaxed)),
+            last_sync_date: Some(checkpoint_date),
+        },
+    )
+}
+
 /// Build or incrementally update the OpenAlex Tantivy index.
 ///
 /// - `since_override`: if set, only download S3 partitions newer than this date (YYYY-MM-DD).
@@ -28,10 +139,14 @@ const ALLOWED_TYPES: &[&str] = &[
 /// - `min_year`: if set, skip works with `publication_year` before this year during indexing.
 ///
 /// Returns `true` if new data was indexed, `false` if already up to date.
+/// - `download_concurrency`: number of S3 files downloaded in parallel. `None`
+///   defaults to the available CPU parallelism, which also sizes the parse
+///   worker pool and the number of tantivy indexing threads.
 pub async fn build(
     db_path: &Path,
     since_override: Option<String>,
     min_year: Option<u32>,
+    download_concurrency: Option<usize>,
     mut progress: impl FnMut(BuildProgress),
 ) -> Result<bool, OpenAlexError> {
     let client = reqwest::Client::builder()
@@ -45,14 +160,35 @@ pub async fn build(
     } else {
         None
     };
+
+    // Step 1: Open or create the Tantivy index, migrating its on-disk schema
+    // version first. This has to happen before `last_sync_date`/`existing_meta`
+    // are used below — an old-schema index filtered incrementally would
+    // silently accumulate documents missing fields the current schema
+    // depends on, rather than getting the full rebuild it actually needs.
+    std::fs::create_dir_all(db_path)?;
+    let (index, schema, open_outcome) = open_or_create_index(db_path)?;
+
+    // A rebuilt index starts empty, so neither the prior last_sync_date
+    // cutoff nor the prior publication_count still apply.
+    let existing_meta = if open_outcome == IndexOpenOutcome::Rebuilt {
+        None
+    } else {
+        existing_meta
+    };
+
     // since_override takes priority over stored last_sync_date
     let last_sync_date = since_override.or_else(|| {
         existing_meta
             .as_ref()
             .and_then(|m| m.last_sync_date.clone())
     });
+    let base_publication_count = existing_meta
+        .as_ref()
+        .and_then(|m| m.publication_count)
+        .unwrap_or(0);
 
-    // Step 1: List date partitions from S3
+    // Step 2: List date partitions from S3
     progress(BuildProgress::ListingPartitions {
         message: "Listing OpenAlex S3 partitions...".to_string(),
     });
@@ -73,112 +209,269 @@ pub async fn build(
         progress(BuildProgress::Complete {
             publications: 0,
             skipped: true,
+            tombstones: 0,
         });
         return Ok(false);
     }
 
     let partitions_total = partitions.len() as u64;
-
-    // Step 2: Open or create Tantivy index
-    std::fs::create_dir_all(db_path)?;
-
-    let (index, schema) = open_or_create_index(db_path)?;
-    let title_field = schema
-        .get_field("title")
-        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-    let authors_field = schema
-        .get_field("authors")
-        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-    let id_field = schema
-        .get_field("openalex_id")
-        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-
-    let mut writer: IndexWriter = index
-        .writer(256_000_000) // 256MB heap
-        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-
-    let mut total_records: u64 = 0;
-    let mut total_bytes: u64 = 0;
     let mut newest_date = last_sync_date.clone().unwrap_or_default();
-    let mut uncommitted_count: u64 = 0;
+    for partition in &partitions {
+        if partition.date > newest_date {
+            newest_date = partition.date.clone();
+        }
+    }
 
-    // Step 3: Process each partition
+    // Step 3: List every file across every partition up front — these are
+    // cheap metadata calls, unlike the downloads themselves — so the
+    // concurrent pipeline below has the full work list to draw from instead
+    // of being bottlenecked on one partition's listing call at a time.
+    let mut file_keys: Vec<(String, String)> = Vec::new(); // (partition date, file key)
     for (part_idx, partition) in partitions.iter().enumerate() {
         progress(BuildProgress::Downloading {
             partitions_done: part_idx as u64,
             partitions_total,
-            bytes_downloaded: total_bytes,
-            records_indexed: total_records,
+            bytes_downloaded: 0,
+            records_indexed: 0,
         });
-
-        // List files in this partition
         let files = s3::list_partition_files(&client, &partition.prefix).await?;
+        file_keys.extend(files.into_iter().map(|f| (partition.date.clone(), f.key)));
+    }
 
-        for file in &files {
-            // Download the gzipped file
-            let gz_bytes: Vec<u8> = s3::download_gz(&client, &file.key).await?;
-            total_bytes += gz_bytes.len() as u64;
+    // Checkpointing bookkeeping: how many files each partition still has
+    // outstanding, and which partitions have fully drained so far. A
+    // partition's checkpoint only fires once every file in it (across every
+    // concurrent worker) has been parsed and indexed.
+    let mut ordered_dates: Vec<String> = partitions.iter().map(|p| p.date.clone()).collect();
+    ordered_dates.sort();
+    let mut remaining_files: HashMap<String, usize> = HashMap::new();
+    for (date, _) in &file_keys {
+        *remaining_files.entry(date.clone()).or_insert(0) += 1;
+    }
+    let remaining_files = Arc::new(Mutex::new(remaining_files));
+    let completed_partitions: Arc<Mutex<HashSet<String>>> = Arc::new(Mutex::new(HashSet::new()));
+
+    let fields = IndexFields {
+        title: schema
+            .get_field("title")
+            .map_err(|e| OpenAlexError::Index(e.to_string()))?,
+        authors: schema
+            .get_field("authors")
+            .map_err(|e| OpenAlexError::Index(e.to_string()))?,
+        institutions: schema
+            .get_field("institutions")
+            .map_err(|e| OpenAlexError::Index(e.to_string()))?,
+        abstract_text: schema
+            .get_field("abstract")
+            .map_err(|e| OpenAlexError::Index(e.to_string()))?,
+        id: schema
+            .get_field("openalex_id")
+            .map_err(|e| OpenAlexError::Index(e.to_string()))?,
+    };
+
+    let index_threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let download_concurrency = download_concurrency.unwrap_or(index_threads).max(1);
+    let batch_count = batch_doc_count(index_threads);
+
+    let writer: IndexWriter = index
+        .writer_with_num_threads(index_threads, WRITER_HEAP_BYTES)
+        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
 
-            // Decompress and parse JSON lines
+    // Step 3b: tombstone works OpenAlex has since merged away or deleted.
+    // Each snapshot partition publishes a merged-ids manifest alongside its
+    // work files — a CSV of `retired_id,canonical_id` rows. Without this,
+    // a work that got folded into another record lingers in the index
+    // under its old id and can produce a false "this citation exists" hit.
+    // Sequential rather than pipelined like the main download/index stage:
+    // these manifests are tiny compared to the work files themselves, and
+    // the writer isn't shared across threads yet at this point.
+    let mut tombstones: u64 = 0;
+    for partition in &partitions {
+        let merged_id_files = s3::list_merged_id_files(&client, &partition.prefix).await?;
+        for file in merged_id_files {
+            let gz_bytes = s3::download_gz(&client, &file.key).await?;
             let decoder = GzDecoder::new(gz_bytes.as_slice());
             let buf_reader = BufReader::new(decoder);
-
             for line_result in buf_reader.lines() {
-                let line: String = match line_result {
-                    Ok(l) => l,
-                    Err(_) => continue,
+                let Ok(line) = line_result else { continue };
+                let Some(retired_id_str) = line.split(',').next() else {
+                    continue;
                 };
-
-                if line.trim().is_empty() {
+                let Some(retired_id) = extract_numeric_id(retired_id_str.trim()) else {
                     continue;
-                }
+                };
+                writer.delete_term(tantivy::Term::from_field_u64(fields.id, retired_id));
+                tombstones += 1;
+            }
+        }
+    }
 
-                if let Some((openalex_id, title, authors)) = parse_work_json(&line, min_year) {
-                    // Upsert: delete existing, then add
-                    let id_term = tantivy::Term::from_field_u64(id_field, openalex_id);
-                    writer.delete_term(id_term);
+    let writer = Arc::new(RwLock::new(writer));
+
+    // Step 4: Concurrent download → parse → index pipeline.
+    //
+    // A bounded set of async download tasks fetches `s3::download_gz`
+    // results into `gz_tx`; a pool of blocking parse workers drains that
+    // channel, decompresses/parses each line with `parse_work_json`, and
+    // calls `add_document` directly against the shared writer (tantivy's
+    // `add_document` takes `&self` and is safe to call concurrently — that's
+    // what `writer_with_num_threads` is for). Every worker takes a *read*
+    // lock to add documents and only a *write* lock to commit, so commits
+    // still serialize against in-flight indexing without needing a
+    // dedicated writer task.
+    let total_bytes = Arc::new(AtomicU64::new(0));
+    let total_records = Arc::new(AtomicU64::new(0));
+    let uncommitted = Arc::new(AtomicU64::new(0));
+    let first_error: Arc<Mutex<Option<OpenAlexError>>> = Arc::new(Mutex::new(None));
+
+    let (gz_tx, gz_rx) = mpsc::channel::<DownloadedFile>(download_concurrency * 2);
+    let gz_rx = Arc::new(Mutex::new(gz_rx));
+
+    let mut downloads = JoinSet::new();
+    {
+        let permits = Arc::new(tokio::sync::Semaphore::new(download_concurrency));
+        for (date, key) in file_keys {
+            let client = client.clone();
+            let tx = gz_tx.clone();
+            let total_bytes = Arc::clone(&total_bytes);
+            let permits = Arc::clone(&permits);
+            downloads.spawn(async move {
+                let _permit = permits.acquire_owned().await.expect("semaphore not closed");
+                let gz_bytes = s3::download_gz(&client, &key).await?;
+                total_bytes.fetch_add(gz_bytes.len() as u64, Ordering::Relaxed);
+                let _ = tx
+                    .send(DownloadedFile {
+                        date,
+                        bytes: gz_bytes,
+                    })
+                    .await;
+                Ok::<(), OpenAlexError>(())
+            });
+        }
+    }
+    drop(gz_tx);
+
+    let db_path_owned = db_path.to_path_buf();
+    let mut parse_workers = JoinSet::new();
+    for _ in 0..index_threads.max(1) {
+        let gz_rx = Arc::clone(&gz_rx);
+        let writer = Arc::clone(&writer);
+        let total_records = Arc::clone(&total_records);
+        let uncommitted = Arc::clone(&uncommitted);
+        let first_error = Arc::clone(&first_error);
+        let remaining_files = Arc::clone(&remaining_files);
+        let completed_partitions = Arc::clone(&completed_partitions);
+        let ordered_dates = ordered_dates.clone();
+        let db_path_owned = db_path_owned.clone();
+        parse_workers.spawn_blocking(move || {
+            loop {
+                if first_error.lock().unwrap().is_some() {
+                    break;
+                }
+                let file = {
+                    let mut rx = gz_rx.lock().unwrap();
+                    rx.blocking_recv()
+                };
+                let Some(file) = file else { break };
+
+                let decoder = GzDecoder::new(file.bytes.as_slice());
+                let buf_reader = BufReader::new(decoder);
+                for line_result in buf_reader.lines() {
+                    let Ok(line) = line_result else { continue };
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let Some((openalex_id, title, authors, institutions, abstract_text)) =
+                        parse_work_json(&line, min_year)
+                    else {
+                        continue;
+                    };
 
+                    let id_term = tantivy::Term::from_field_u64(fields.id, openalex_id);
                     let authors_str = authors.join("|");
-                    writer
-                        .add_document(doc!(
-                            title_field => title,
-                            authors_field => authors_str,
-                            id_field => openalex_id,
+                    let institutions_str = institutions.join("|");
+                    let add_result = {
+                        let w = writer.read().unwrap();
+                        w.delete_term(id_term);
+                        w.add_document(doc!(
+                            fields.title => title,
+                            fields.authors => authors_str,
+                            fields.institutions => institutions_str,
+                            fields.abstract_text => abstract_text.unwrap_or_default(),
+                            fields.id => openalex_id,
                         ))
-                        .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-
-                    total_records += 1;
-                    uncommitted_count += 1;
-
-                    // Commit periodically
-                    if uncommitted_count >= 100_000 {
-                        progress(BuildProgress::Committing {
-                            records_indexed: total_records,
-                        });
-                        writer
-                            .commit()
-                            .map_err(|e| OpenAlexError::Index(e.to_string()))?;
-                        uncommitted_count = 0;
+                    };
+                    if let Err(e) = add_result {
+                        *first_error.lock().unwrap() = Some(OpenAlexError::Index(e.to_string()));
+                        return;
+                    }
+
+                    total_records.fetch_add(1, Ordering::Relaxed);
+                    let pending = uncommitted.fetch_add(1, Ordering::Relaxed) + 1;
+                    if pending >= batch_count
+                        && uncommitted
+                            .compare_exchange(pending, 0, Ordering::SeqCst, Ordering::SeqCst)
+                            .is_ok()
+                    {
+                        if let Err(e) = commit_writer(&writer, &uncommitted) {
+                            *first_error.lock().unwrap() = Some(e);
+                            return;
+                        }
+                    }
+                }
+
+                let partition_done = {
+                    let mut remaining = remaining_files.lock().unwrap();
+                    let count = remaining.entry(file.date.clone()).or_insert(0);
+                    *count = count.saturating_sub(1);
+                    *count == 0
+                };
+                if partition_done {
+                    if let Err(e) = checkpoint_partition(
+                        &db_path_owned,
+                        &ordered_dates,
+                        &completed_partitions,
+                        &file.date,
+                        &writer,
+                        &uncommitted,
+                        &total_records,
+                        base_publication_count,
+                    ) {
+                        *first_error.lock().unwrap() = Some(e);
+                        return;
                     }
                 }
             }
+        });
+    }
 
-            // Update progress after each file
-            progress(BuildProgress::Downloading {
-                partitions_done: part_idx as u64,
-                partitions_total,
-                bytes_downloaded: total_bytes,
-                records_indexed: total_records,
-            });
+    while let Some(result) = downloads.join_next().await {
+        if let Ok(Err(e)) = result {
+            first_error.lock().unwrap().get_or_insert(e);
         }
+        progress(BuildProgress::Downloading {
+            partitions_done: partitions_total,
+            partitions_total,
+            bytes_downloaded: total_bytes.load(Ordering::Relaxed),
+            records_indexed: total_records.load(Ordering::Relaxed),
+        });
+    }
+    while parse_workers.join_next().await.is_some() {}
 
-        if partition.date > newest_date {
-            newest_date = partition.date.clone();
-        }
+    if let Some(e) = first_error.lock().unwrap().take() {
+        return Err(e);
     }
 
-    // Step 4: Final commit
-    if uncommitted_count > 0 {
+    let mut writer = Arc::try_unwrap(writer)
+        .unwrap_or_else(|_| panic!("all parse workers have exited by this point"))
+        .into_inner()
+        .unwrap();
+
+    // Step 5: Final commit.
+    let total_records = total_records.load(Ordering::Relaxed);
+    if uncommitted.load(Ordering::Relaxed) > 0 {
         progress(BuildProgress::Committing {
             records_indexed: total_records,
         });
@@ -187,13 +480,13 @@ pub async fn build(
             .map_err(|e| OpenAlexError::Index(e.to_string()))?;
     }
 
-    // Step 5: Wait for merge threads
+    // Step 6: Wait for merge threads
     progress(BuildProgress::Merging);
     writer
         .wait_merging_threads()
         .map_err(|e| OpenAlexError::Index(e.to_string()))?;
 
-    // Step 6: Write updated metadata
+    // Step 7: Write updated metadata
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
@@ -205,7 +498,7 @@ pub async fn build(
     metadata::write_metadata(
         db_path,
         &IndexMetadata {
-            schema_version: "1".to_string(),
+            schema_version: CURRENT_SCHEMA_VERSION.to_string(),
             build_date: Some(now.to_string()),
             publication_count: Some(total_in_index),
             last_sync_date: Some(newest_date),
@@ -215,40 +508,174 @@ pub async fn build(
     progress(BuildProgress::Complete {
         publications: total_records,
         skipped: false,
+        tombstones,
     });
 
     Ok(true)
 }
 
-/// Open an existing Tantivy index or create a new one with our schema.
-fn open_or_create_index(path: &Path) -> Result<(Index, Schema), OpenAlexError> {
-    // Check if this is already a Tantivy index directory
+/// Current on-disk Tantivy schema version. Bump this whenever
+/// [`build_schema`] changes in a way an existing index can't transparently
+/// absorb (a new field, a changed field type, ...) and register the
+/// corresponding step in [`SCHEMA_MIGRATIONS`].
+const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// What an on-disk index needs to reach [`CURRENT_SCHEMA_VERSION`]. Tantivy
+/// schemas are immutable once created, so there's no in-place "add a column"
+/// path the way a SQL migration would have: a step is either `Additive`
+/// (the new fields are simply absent from old documents until the next
+/// rebuild touches them — no on-disk action needed) or `Rebuild` (old
+/// documents are missing data the new schema depends on, so the index must
+/// be recreated from empty and everything reindexed).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaMigration {
+    Additive,
+    Rebuild,
+}
+
+/// Registered migration steps, keyed by the *target* version each one
+/// produces. `(2, Rebuild)` means "migrating to v2 requires a rebuild".
+const SCHEMA_MIGRATIONS: &[(u32, SchemaMigration)] =
+    &[(2, SchemaMigration::Rebuild)]; // v1 → v2: added `institutions` and `abstract` fields
+
+/// Outcome of [`open_or_create_index`], so callers can tell a from-scratch
+/// rebuild apart from a plain reopen (a rebuild invalidates any prior
+/// `last_sync_date`/`publication_count` bookkeeping).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexOpenOutcome {
+    Fresh,
+    UpToDate,
+    Rebuilt,
+}
+
+/// Walk the registered [`SCHEMA_MIGRATIONS`] from `on_disk_version` up to
+/// [`CURRENT_SCHEMA_VERSION`], returning the combined action needed (a
+/// `Rebuild` anywhere in the path means the whole thing is a rebuild).
+/// Errors if the on-disk version is newer than this build supports, or if no
+/// migration is registered for some step in the path.
+fn plan_schema_migration(on_disk_version: u32) -> Result<SchemaMigration, OpenAlexError> {
+    if on_disk_version > CURRENT_SCHEMA_VERSION {
+        return Err(OpenAlexError::Index(format!(
+            "on-disk OpenAlex index is schema v{on_disk_version}, newer than this build supports (v{CURRENT_SCHEMA_VERSION})"
+        )));
+    }
+
+    let mut outcome = SchemaMigration::Additive;
+    for target in (on_disk_version + 1)..=CURRENT_SCHEMA_VERSION {
+        let step = SCHEMA_MIGRATIONS
+            .iter()
+            .find(|(version, _)| *version == target)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| {
+                OpenAlexError::Index(format!(
+                    "no migration path registered from OpenAlex index schema v{on_disk_version} to v{target}"
+                ))
+            })?;
+        if step == SchemaMigration::Rebuild {
+            outcome = SchemaMigration::Rebuild;
+        }
+    }
+    Ok(outcome)
+}
+
+/// Open an existing Tantivy index, migrating it first if its stored schema
+/// version is behind [`CURRENT_SCHEMA_VERSION`], or create a new one.
+fn open_or_create_index(path: &Path) -> Result<(Index, Schema, IndexOpenOutcome), OpenAlexError> {
     let meta_path = path.join("meta.json");
-    if meta_path.exists() {
-        let index = Index::open_in_dir(path)?;
-        let schema = index.schema();
-        return Ok((index, schema));
+    if !meta_path.exists() {
+        let schema = build_schema();
+        let index = Index::create_in_dir(path, schema.clone())?;
+        return Ok((index, schema, IndexOpenOutcome::Fresh));
     }
 
-    // Create new index with schema
-    let schema = build_schema();
-    let index = Index::create_in_dir(path, schema.clone())?;
-    Ok((index, schema))
+    let on_disk_version = metadata::read_metadata(path)
+        .ok()
+        .and_then(|m| m.schema_version.parse::<u32>().ok())
+        .unwrap_or(1);
+
+    match plan_schema_migration(on_disk_version)? {
+        SchemaMigration::Additive => {
+            let index = Index::open_in_dir(path)?;
+            let schema = index.schema();
+            Ok((index, schema, IndexOpenOutcome::UpToDate))
+        }
+        SchemaMigration::Rebuild => {
+            std::fs::remove_dir_all(path)?;
+            std::fs::create_dir_all(path)?;
+            let schema = build_schema();
+            let index = Index::create_in_dir(path, schema.clone())?;
+            Ok((index, schema, IndexOpenOutcome::Rebuilt))
+        }
+    }
 }
 
 fn build_schema() -> Schema {
     let mut schema_builder = Schema::builder();
     schema_builder.add_text_field("title", TEXT | STORED);
     schema_builder.add_text_field("authors", STORED);
+    schema_builder.add_text_field("institutions", TEXT | STORED);
+    schema_builder.add_text_field("abstract", TEXT | STORED);
     schema_builder.add_u64_field("openalex_id", INDEXED | STORED | FAST);
     schema_builder.build()
 }
 
-/// Parse a single OpenAlex JSON line into (openalex_id, title, authors).
+/// Maximum inverted-index token count we'll reconstruct an abstract from;
+/// guards against a pathological work with an enormous `abstract_inverted_index`
+/// spending unbounded time/memory on a single document.
+const MAX_ABSTRACT_TOKENS: usize = 20_000;
+
+/// Reconstruct an OpenAlex `abstract_inverted_index` (token -> positions) back
+/// into plain text. Positions are 0-based and need not be dense or ordered;
+/// any position left unfilled (a gap in the index) is simply skipped rather
+/// than rendered as a blank slot.
+fn reconstruct_abstract(inverted_index: &serde_json::Value) -> Option<String> {
+    let entries = inverted_index.as_object()?;
+    if entries.is_empty() || entries.len() > MAX_ABSTRACT_TOKENS {
+        return None;
+    }
+
+    let mut max_position = 0usize;
+    for positions in entries.values() {
+        for pos in positions.as_array()?.iter() {
+            max_position = max_position.max(pos.as_u64()? as usize);
+        }
+    }
+    // A handful of tokens can still carry an absurd position (corrupt or
+    // adversarial input), and `max_position` alone drives the allocation
+    // below — bound it too, not just the token count, or a single bogus
+    // position can demand a multi-terabyte `Vec`.
+    if max_position >= MAX_ABSTRACT_TOKENS {
+        return None;
+    }
+
+    let mut slots: Vec<Option<&str>> = vec![None; max_position + 1];
+    for (word, positions) in entries {
+        for pos in positions.as_array()?.iter() {
+            let idx = pos.as_u64()? as usize;
+            slots[idx] = Some(word.as_str());
+        }
+    }
+
+    let text = slots.into_iter().flatten().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Parse a single OpenAlex JSON line into
+/// (openalex_id, title, authors, institutions, abstract).
 ///
 /// Returns `None` if the work type is not in `ALLOWED_TYPES` or required
-/// fields are missing.
-fn parse_work_json(line: &str, min_year: Option<u32>) -> Option<(u64, String, Vec<String>)> {
+/// fields are missing. The abstract is `None` when the work has no
+/// `abstract_inverted_index`, which is common and not itself a parse failure.
+/// Institutions are deduped per-work; an authorship with an empty or missing
+/// `institutions` array simply contributes none.
+fn parse_work_json(
+    line: &str,
+    min_year: Option<u32>,
+) -> Option<(u64, String, Vec<String>, Vec<String>, Option<String>)> {
     let value: serde_json::Value = serde_json::from_str(line).ok()?;
 
     // Filter by type
@@ -276,9 +703,8 @@ fn parse_work_json(line: &str, min_year: Option<u32>) -> Option<(u64, String, Ve
     let openalex_id = extract_numeric_id(id_str)?;
 
     // Extract authors
-    let authors: Vec<String> = value
-        .get("authorships")
-        .and_then(|a| a.as_array())
+    let authorships = value.get("authorships").and_then(|a| a.as_array());
+    let authors: Vec<String> = authorships
         .map(|arr| {
             arr.iter()
                 .filter_map(|a| {
@@ -291,7 +717,42 @@ fn parse_work_json(line: &str, min_year: Option<u32>) -> Option<(u64, String, Ve
         })
         .unwrap_or_default();
 
-    Some((openalex_id, title.to_string(), authors))
+    // Extract institution affiliations (name + ROR id), deduped per-work.
+    // An authorship with an empty or missing `institutions` array contributes
+    // nothing — it's not an error, just an author with no listed affiliation.
+    let mut institution_set: std::collections::BTreeSet<String> = std::collections::BTreeSet::new();
+    if let Some(arr) = authorships {
+        for authorship in arr {
+            let Some(institutions) = authorship.get("institutions").and_then(|i| i.as_array())
+            else {
+                continue;
+            };
+            for institution in institutions {
+                let Some(name) = institution.get("display_name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                let entry = match institution.get("ror").and_then(|r| r.as_str()) {
+                    Some(ror) => format!("{} ({})", name, ror),
+                    None => name.to_string(),
+                };
+                institution_set.insert(entry);
+            }
+        }
+    }
+    let institutions: Vec<String> = institution_set.into_iter().collect();
+
+    let abstract_text = value
+        .get("abstract_inverted_index")
+        .filter(|v| !v.is_null())
+        .and_then(reconstruct_abstract);
+
+    Some((
+        openalex_id,
+        title.to_string(),
+        authors,
+        institutions,
+        abstract_text,
+    ))
 }
 
 /// Extract numeric ID from OpenAlex URL: "https://openalex.org/W1234567" â†’ 1234567
@@ -312,10 +773,64 @@ mod tests {
         let json = r#"{"id":"https://openalex.org/W2741809807","display_name":"Attention is All you Need","type":"article","authorships":[{"author":{"display_name":"Ashish Vaswani"}},{"author":{"display_name":"Noam Shazeer"}}]}"#;
         let result = parse_work_json(json, None);
         assert!(result.is_some());
-        let (id, title, authors) = result.unwrap();
+        let (id, title, authors, institutions, abstract_text) = result.unwrap();
         assert_eq!(id, 2741809807);
         assert_eq!(title, "Attention is All you Need");
         assert_eq!(authors, vec!["Ashish Vaswani", "Noam Shazeer"]);
+        assert!(institutions.is_empty());
+        assert_eq!(abstract_text, None);
+    }
+
+    #[test]
+    fn test_parse_work_json_reconstructs_abstract() {
+        let json = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[],"abstract_inverted_index":{"Deep":[0],"learning":[1],"is":[2],"powerful":[3]}}"#;
+        let (_, _, _, _, abstract_text) = parse_work_json(json, None).unwrap();
+        assert_eq!(abstract_text, Some("Deep learning is powerful".to_string()));
+    }
+
+    #[test]
+    fn test_parse_work_json_abstract_handles_gaps_and_null() {
+        let sparse = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[],"abstract_inverted_index":{"first":[0],"last":[3]}}"#;
+        let (_, _, _, _, abstract_text) = parse_work_json(sparse, None).unwrap();
+        assert_eq!(abstract_text, Some("first last".to_string()));
+
+        let null_index = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[],"abstract_inverted_index":null}"#;
+        let (_, _, _, _, abstract_text) = parse_work_json(null_index, None).unwrap();
+        assert_eq!(abstract_text, None);
+
+        let missing = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[]}"#;
+        let (_, _, _, _, abstract_text) = parse_work_json(missing, None).unwrap();
+        assert_eq!(abstract_text, None);
+    }
+
+    #[test]
+    fn test_parse_work_json_rejects_absurd_token_position() {
+        // A handful of tokens, but one carries a position far beyond
+        // MAX_ABSTRACT_TOKENS — the count guard alone wouldn't catch this,
+        // since `vec![None; max_position + 1]` is driven by the position,
+        // not the token count.
+        let json = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[],"abstract_inverted_index":{"word":[999999999999]}}"#;
+        let (_, _, _, _, abstract_text) = parse_work_json(json, None).unwrap();
+        assert_eq!(abstract_text, None);
+    }
+
+    #[test]
+    fn test_parse_work_json_collects_institutions() {
+        let json = r#"{"id":"https://openalex.org/W1","display_name":"Test","type":"article","authorships":[
+            {"author":{"display_name":"A"},"institutions":[{"display_name":"CERN","ror":"https://ror.org/01ggx4157"}]},
+            {"author":{"display_name":"B"},"institutions":[{"display_name":"CERN","ror":"https://ror.org/01ggx4157"}]},
+            {"author":{"display_name":"C"},"institutions":[{"display_name":"MIT"}]},
+            {"author":{"display_name":"D"},"institutions":[]},
+            {"author":{"display_name":"E"}}
+        ]}"#;
+        let (_, _, _, institutions, _) = parse_work_json(json, None).unwrap();
+        assert_eq!(
+            institutions,
+            vec![
+                "CERN (https://ror.org/01ggx4157)".to_string(),
+                "MIT".to_string(),
+            ]
+        );
     }
 
     #[test]
@@ -372,4 +887,58 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_plan_schema_migration_up_to_date() {
+        assert_eq!(
+            plan_schema_migration(CURRENT_SCHEMA_VERSION).unwrap(),
+            SchemaMigration::Additive
+        );
+    }
+
+    #[test]
+    fn test_plan_schema_migration_requires_rebuild() {
+        assert_eq!(plan_schema_migration(1).unwrap(), SchemaMigration::Rebuild);
+    }
+
+    #[test]
+    fn test_plan_schema_migration_rejects_newer_on_disk_version() {
+        assert!(plan_schema_migration(CURRENT_SCHEMA_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn test_plan_schema_migration_errors_on_unregistered_step() {
+        assert!(plan_schema_migration(0).is_err());
+    }
+
+    #[test]
+    fn test_contiguous_checkpoint_advances_only_through_a_gap_free_prefix() {
+        let dates = vec![
+            "2024-01-01".to_string(),
+            "2024-01-02".to_string(),
+            "2024-01-03".to_string(),
+        ];
+        let mut completed = HashSet::new();
+        assert_eq!(contiguous_checkpoint(&dates, &completed), None);
+
+        completed.insert("2024-01-01".to_string());
+        assert_eq!(
+            contiguous_checkpoint(&dates, &completed),
+            Some("2024-01-01".to_string())
+        );
+
+        // Out-of-order completion: the third partition finishing before the
+        // second must not advance the checkpoint past the gap.
+        completed.insert("2024-01-03".to_string());
+        assert_eq!(
+            contiguous_checkpoint(&dates, &completed),
+            Some("2024-01-01".to_string())
+        );
+
+        completed.insert("2024-01-02".to_string());
+        assert_eq!(
+            contiguous_checkpoint(&dates, &completed),
+            Some("2024-01-03".to_string())
+        );
+    }
 }
@@ -0,0 +1,143 @@
+//! Runtime configuration for this crate's text-cleaning passes.
+//!
+//! [`ParsingConfig`] is built through [`ParsingConfigBuilder`] rather than
+//! constructed directly, so new knobs can be added here later without
+//! breaking existing callers. [`text_processing::fix_hyphenation_with_config`]
+//! is the one function that reads every field on it, so composing e.g. a
+//! custom compound-suffix list with a lexicon just means setting both on the
+//! same builder before calling it once.
+
+use std::sync::Arc;
+
+use crate::dictionary::Dictionary;
+use crate::text_processing::{HyphenationExceptions, Language};
+
+/// How a list-valued setting relates to its built-in default: append to it,
+/// or replace it outright. Mirrors the two compound-suffix builder methods
+/// (`add_compound_suffix` appends, `set_compound_suffixes` replaces).
+#[derive(Debug, Clone)]
+pub(crate) enum ListOverride {
+    Extend(Vec<String>),
+    Replace(Vec<String>),
+}
+
+impl ListOverride {
+    /// Combine this override with `defaults`: append `Extend`'s entries
+    /// after them, or ignore them entirely for `Replace`.
+    pub(crate) fn resolve(&self, defaults: &[String]) -> Vec<String> {
+        match self {
+            ListOverride::Extend(extra) => {
+                defaults.iter().cloned().chain(extra.iter().cloned()).collect()
+            }
+            ListOverride::Replace(replacement) => replacement.clone(),
+        }
+    }
+}
+
+impl Default for ListOverride {
+    fn default() -> Self {
+        ListOverride::Extend(Vec::new())
+    }
+}
+
+/// Configuration for [`text_processing::fix_hyphenation_with_config`] and the
+/// other config-aware cleaning passes. Build one with [`ParsingConfigBuilder`].
+#[derive(Clone, Default)]
+pub struct ParsingConfig {
+    pub(crate) compound_suffixes: ListOverride,
+    pub(crate) lexicon: Option<Arc<dyn Dictionary>>,
+    pub(crate) exceptions: HyphenationExceptions,
+    pub(crate) language: Language,
+}
+
+/// Builder for [`ParsingConfig`].
+#[derive(Default)]
+pub struct ParsingConfigBuilder {
+    compound_suffixes: ListOverride,
+    lexicon: Option<Arc<dyn Dictionary>>,
+    exceptions: HyphenationExceptions,
+    language: Language,
+}
+
+impl ParsingConfigBuilder {
+    /// Start from the built-in defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `suffix` to the built-in compound-suffix list.
+    pub fn add_compound_suffix(mut self, suffix: String) -> Self {
+        match &mut self.compound_suffixes {
+            ListOverride::Extend(suffixes) => suffixes.push(suffix),
+            ListOverride::Replace(suffixes) => suffixes.push(suffix),
+        }
+        self
+    }
+
+    /// Replace the built-in compound-suffix list with `suffixes` entirely.
+    pub fn set_compound_suffixes(mut self, suffixes: Vec<String>) -> Self {
+        self.compound_suffixes = ListOverride::Replace(suffixes);
+        self
+    }
+
+    /// Attach a lexicon that [`text_processing::fix_hyphenation_with_config`]
+    /// consults for otherwise-ambiguous splits, the same way
+    /// [`text_processing::fix_hyphenation_with_lexicon`] does for a lexicon
+    /// passed directly.
+    pub fn with_lexicon(mut self, lexicon: Arc<dyn Dictionary>) -> Self {
+        self.lexicon = Some(lexicon);
+        self
+    }
+
+    /// Pin one `before`/`after` hyphenation split to `result`, same as
+    /// [`HyphenationExceptions::add_hyphenation_exception`]. Can be called
+    /// repeatedly to build up the table entry by entry.
+    pub fn add_hyphenation_exception(mut self, before: &str, after: &str, result: &str) -> Self {
+        self.exceptions.add_hyphenation_exception(before, after, result);
+        self
+    }
+
+    /// Replace the whole exception table with `entries`, same as
+    /// [`HyphenationExceptions::set_hyphenation_exceptions`].
+    pub fn set_hyphenation_exceptions(
+        mut self,
+        entries: impl IntoIterator<Item = (String, String, String)>,
+    ) -> Self {
+        self.exceptions = HyphenationExceptions::set_hyphenation_exceptions(entries);
+        self
+    }
+
+    /// Set the source language fed to
+    /// [`text_processing::fix_hyphenation_with_config`], same as
+    /// [`text_processing::fix_hyphenation_with_language`]'s `language`
+    /// argument. Defaults to [`Language::English`].
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Finish building. Infallible today; returns `Result` so future
+    /// validation (e.g. conflicting suffix/exception entries) can be added
+    /// without breaking callers.
+    pub fn build(self) -> Result<ParsingConfig, ParsingConfigError> {
+        Ok(ParsingConfig {
+            compound_suffixes: self.compound_suffixes,
+            lexicon: self.lexicon,
+            exceptions: self.exceptions,
+            language: self.language,
+        })
+    }
+}
+
+/// Error building a [`ParsingConfig`]. No [`ParsingConfigBuilder`] method
+/// produces one yet; reserved for future validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsingConfigError(String);
+
+impl std::fmt::Display for ParsingConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParsingConfigError {}
@@ -12,4 +12,17 @@ pub trait Dictionary: Send + Sync {
     ///
     /// Implementations should perform case-insensitive lookups.
     fn contains(&self, word: &str) -> bool;
+
+    /// Find the closest dictionary word to `word` within `max_edits`
+    /// character edits, or `None` if nothing in the dictionary is close
+    /// enough. Intended for snapping OCR/extraction noise (a dropped
+    /// letter, a lost ligature) back to a real word before giving up on it.
+    ///
+    /// The default implementation performs no correction; dictionaries that
+    /// can support fuzzy lookups efficiently (e.g. via an FST) should
+    /// override it.
+    fn correct(&self, word: &str, max_edits: u8) -> Option<String> {
+        let _ = (word, max_edits);
+        None
+    }
 }
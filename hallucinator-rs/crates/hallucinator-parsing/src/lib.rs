@@ -0,0 +1,14 @@
+//! Text-cleaning and dictionary-validation primitives for text extracted
+//! from PDFs: ligature expansion, hyphenation repair, and the
+//! [`Dictionary`] trait those passes validate merges/splits against.
+pub mod config;
+pub mod dictionary;
+pub mod text_processing;
+
+pub use config::{ParsingConfig, ParsingConfigBuilder, ParsingConfigError};
+pub use dictionary::Dictionary;
+pub use text_processing::{
+    expand_ligatures, expand_ligatures_for_language, fix_hyphenation, fix_hyphenation_with_dict,
+    fix_hyphenation_with_exceptions, fix_hyphenation_with_language, fix_hyphenation_with_lexicon,
+    HyphenationExceptions, Language,
+};
@@ -1,8 +1,9 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::config::ParsingConfig;
+use crate::Dictionary;
 
 /// Common compound-word suffixes that should keep the hyphen.
 pub(crate) static COMPOUND_SUFFIXES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
@@ -95,6 +96,34 @@ pub fn expand_ligatures(text: &str) -> String {
         .replace(['\u{FB05}', '\u{FB06}'], "st")
 }
 
+/// Source language of the text being cleaned, used to select
+/// language-specific ligature and hyphenation rules.
+///
+/// Defaults to [`Language::English`], so existing callers of
+/// [`expand_ligatures`] and [`fix_hyphenation`] (which are English-only and
+/// unaffected by this enum) see no change in behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    Dutch,
+}
+
+/// Language-aware version of [`expand_ligatures`].
+///
+/// Always expands the Latin f-ligatures handled by `expand_ligatures`, then
+/// layers on language-specific substitutions: German's long s (`ſ`, a purely
+/// stylistic variant of `s`), and Dutch's `ĳ`/`Ĳ` digraph letter.
+pub fn expand_ligatures_for_language(text: &str, language: Language) -> String {
+    let text = expand_ligatures(text);
+    match language {
+        Language::English => text,
+        Language::German => text.replace('\u{017F}', "s"),
+        Language::Dutch => text.replace('\u{0132}', "IJ").replace('\u{0133}', "ij"),
+    }
+}
+
 /// Fix hyphenation from PDF line breaks while preserving compound words.
 ///
 /// - `"detec- tion"` or `"detec-\ntion"` → `"detection"` (syllable break)
@@ -103,34 +132,255 @@ pub fn fix_hyphenation(text: &str) -> String {
     fix_hyphenation_with_config(text, &ParsingConfig::default())
 }
 
-/// Common syllable-break suffixes that indicate a word was split mid-syllable.
-/// These should trigger merging even when both parts are ≥4 letters.
-static SYLLABLE_SUFFIXES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+/// Language-aware version of [`fix_hyphenation_with_config`].
+///
+/// [`Language::English`] runs the full suffix-table, pattern-engine, and
+/// connector-word heuristics as before. The other languages don't yet have
+/// a populated compound-suffix list, syllable-break pattern table, or
+/// connector-word list of their own — `COMPOUND_SUFFIXES`, the English
+/// `HYPHEN_CONNECTORS` list, and `DEFAULT_PATTERNS` are English-specific and
+/// are skipped for them — so non-English text falls back to the short-affix
+/// length heuristic, plus any [`HyphenationExceptions`] or lexicon the
+/// caller supplies via [`fix_hyphenation_with_exceptions`]/
+/// [`fix_hyphenation_with_lexicon`].
+pub fn fix_hyphenation_with_language(text: &str, config: &ParsingConfig, language: Language) -> String {
+    fix_hyphenation_inner(text, config, None, None, language)
+}
+
+/// A single Knuth–Liang hyphenation pattern, e.g. `"hen5at"` or `".ph2o"`.
+///
+/// `letters` holds the pattern's letters (with any `.` word-boundary anchor
+/// kept as a literal character, matched against a `.`-wrapped candidate).
+/// `values[i]` is the hyphenation value at the gap immediately before
+/// `letters[i]`, with one trailing value after the last letter — a digit in
+/// the source string sets the gap to its left, and a gap with no digit is
+/// implicitly `0`.
+struct HyphenPattern {
+    letters: Vec<char>,
+    values: Vec<u8>,
+}
+
+impl HyphenPattern {
+    fn parse(pattern: &str) -> Self {
+        let mut letters = Vec::new();
+        let mut values = vec![0u8];
+        for c in pattern.chars() {
+            if let Some(d) = c.to_digit(10) {
+                *values.last_mut().expect("values is never empty") = d as u8;
+            } else {
+                letters.push(c);
+                values.push(0);
+            }
+        }
+        HyphenPattern { letters, values }
+    }
+}
+
+/// Built-in hyphenation patterns, one per syllable-break suffix.
+///
+/// Each suffix `s` becomes the pattern `"1{s}"`: an odd value (a legal break)
+/// right before the suffix begins, e.g. `"1tion"` marks the gap in
+/// `"detec|tion"` as a real syllable boundary. This is a hand-curated subset
+/// tuned for academic/scientific vocabulary — not the full ~4500-pattern
+/// `hyph-en-us.tex` table, which isn't available in this tree — but it plugs
+/// into the same general Liang matching algorithm as the real thing, and
+/// words it has no opinion about simply get no match (see
+/// [`fix_hyphenation_with_config`] for what happens then).
+static DEFAULT_PATTERNS: Lazy<Vec<HyphenPattern>> = Lazy::new(|| {
     [
         // Common word endings that are almost never standalone compound parts
         "tion", "tions", "sion", "sions", "ment", "ments", "ness", "ance", "ence",
         "ency", "ity", "able", "ible", "ous", "ious", "eous", "ive", "ical", "ally",
         "ular", "ology", "ization", "ised", "ized", "ises", "izes", "uous", "ling",
         "ward", "wards", "erly", "ween", "tween", "fore", "hind", "ntic", "mous",
-        "uous", "cial", "tial", "cious", "tious", "gion", "ntic", "rupt", "duct",
+        "cial", "tial", "cious", "tious", "gion", "rupt", "duct",
         "struct", "tract", "gress", "plete", "clude", "sume", "duce", "fect",
         "ject", "rect", "lect", "nect", "tect", "dict", "flict", "strict",
         // Extended syllable patterns (longer suffixes from word breaks)
         "fication", "ification", "ation", "ution", "ction", "ption",
-        "ering", "uring", "ating", "iting", "uting", "eting", "ling",
-        "ness", "less", "ment", "ence", "ance", "ible", "able",
-        "ture", "sure", "ture", "dure", "sure",
-        "ical", "ular", "eous", "ious",
+        "ering", "uring", "ating", "iting", "uting", "eting",
+        "less", "ture", "sure", "dure",
         // Additional patterns found in testing
         "mentation", "putation", "mization", "tication", "rization",
         "tation", "cation", "sation", "nation",
     ]
     .into_iter()
+    .map(|suffix| HyphenPattern::parse(&format!("1{suffix}")))
     .collect()
 });
 
-/// Config-aware version of [`fix_hyphenation`].
+/// Explicit break-point overrides, keyed by lowercased merged word.
+///
+/// Takes priority over [`DEFAULT_PATTERNS`] when present, mirroring the
+/// exceptions file (`hyphen.tex`) that real TeX hyphenation ships alongside
+/// its patterns for words the general rules get wrong. Empty for now; a
+/// follow-up will let callers populate this from config.
+static EXCEPTIONS: Lazy<HashMap<&'static str, &'static [usize]>> = Lazy::new(HashMap::new);
+
+/// Compute the Liang per-gap hyphenation values for `word`.
+///
+/// Wraps `word` as `.word.`, matches every pattern as a substring at every
+/// offset, and keeps the per-gap maximum across all matches — the standard
+/// Knuth–Liang scoring rule. Returned index `0` is the gap before `word`'s
+/// first letter (matching the leading `.`'s trailing gap), so gap `k` for
+/// `0 <= k <= word.chars().count()` is the break point after the `k`-th
+/// letter of `word` itself.
+fn hyphenation_values(word: &str, patterns: &[HyphenPattern]) -> Vec<u8> {
+    let mut wrapped = vec!['.'];
+    wrapped.extend(word.chars());
+    wrapped.push('.');
+
+    let mut points = vec![0u8; wrapped.len() + 1];
+    for pattern in patterns {
+        let plen = pattern.letters.len();
+        if plen == 0 || plen > wrapped.len() {
+            continue;
+        }
+        for start in 0..=wrapped.len() - plen {
+            if wrapped[start..start + plen] == pattern.letters[..] {
+                for (i, &v) in pattern.values.iter().enumerate() {
+                    let idx = start + i;
+                    if v > points[idx] {
+                        points[idx] = v;
+                    }
+                }
+            }
+        }
+    }
+    // Drop the gap before the leading '.' so index 0 lines up with the gap
+    // before `word`'s first letter.
+    points.drain(1..).collect()
+}
+
+/// Legal hyphenation break points within `word` (gap `k` = after the `k`-th
+/// letter), per the Knuth–Liang algorithm: an odd value at a gap means a
+/// real syllable boundary, subject to the usual minimum of two letters on
+/// either side of the break.
+fn legal_break_points(word: &str) -> HashSet<usize> {
+    let lower = word.to_lowercase();
+    if let Some(points) = EXCEPTIONS.get(lower.as_str()) {
+        return points.iter().copied().collect();
+    }
+
+    let len = lower.chars().count();
+    if len < 4 {
+        return HashSet::new();
+    }
+    let values = hyphenation_values(&lower, &DEFAULT_PATTERNS);
+    (2..=len - 2).filter(|&k| values[k] % 2 == 1).collect()
+}
+
+/// User-pinned overrides for specific observed hyphen splits, consulted
+/// before any suffix table or pattern-engine heuristic in
+/// [`fix_hyphenation_with_exceptions`].
+///
+/// Mirrors the exception dictionaries shipped alongside Liang-style
+/// hyphenators (and spaCy's tokenizer-exception tables): pin an exact
+/// `before`/`after` split to its canonical form so domain-specific terms the
+/// general heuristics get wrong — both forced merges (`"multi- plication"`
+/// → `"multiplication"`) and forced compounds (`"e- commerce"` →
+/// `"e-commerce"`) — don't need an entry in `COMPOUND_SUFFIXES` or
+/// [`DEFAULT_PATTERNS`] of their own.
+#[derive(Debug, Default, Clone)]
+pub struct HyphenationExceptions {
+    entries: HashMap<(String, String), String>,
+}
+
+impl HyphenationExceptions {
+    /// Start from an empty exception table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the whole table with `entries` (each `(before, after, result)`).
+    pub fn set_hyphenation_exceptions(
+        entries: impl IntoIterator<Item = (String, String, String)>,
+    ) -> Self {
+        let mut table = Self::new();
+        for (before, after, result) in entries {
+            table.add_hyphenation_exception(&before, &after, &result);
+        }
+        table
+    }
+
+    /// Pin `before`-`after` to `result`, overwriting any existing entry. The
+    /// lookup key is case-folded, matching how `COMPOUND_SUFFIXES` already
+    /// lowercases before comparing.
+    pub fn add_hyphenation_exception(&mut self, before: &str, after: &str, result: &str) -> &mut Self {
+        self.entries
+            .insert((before.to_lowercase(), after.to_lowercase()), result.to_string());
+        self
+    }
+
+    fn lookup(&self, before: &str, after: &str) -> Option<&str> {
+        self.entries
+            .get(&(before.to_lowercase(), after.to_lowercase()))
+            .map(String::as_str)
+    }
+}
+
+/// Config-aware version of [`fix_hyphenation`]. Unlike
+/// [`fix_hyphenation_with_lexicon`]/[`fix_hyphenation_with_exceptions`]/
+/// [`fix_hyphenation_with_language`], which each only layer on one extra
+/// capability, this reads every knob set on `config` — a lexicon attached
+/// via [`ParsingConfigBuilder::with_lexicon`], an exception table built via
+/// [`ParsingConfigBuilder::add_hyphenation_exception`]/
+/// [`ParsingConfigBuilder::set_hyphenation_exceptions`], and a language set
+/// via [`ParsingConfigBuilder::language`] — so they can be composed on a
+/// single [`ParsingConfig`] instead of requiring a separate function call
+/// per capability.
 pub(crate) fn fix_hyphenation_with_config(text: &str, config: &ParsingConfig) -> String {
+    fix_hyphenation_inner(
+        text,
+        config,
+        Some(&config.exceptions),
+        config.lexicon.as_deref(),
+        config.language,
+    )
+}
+
+/// Lexicon-aware version of [`fix_hyphenation_with_config`], in the same
+/// spirit as [`fix_hyphenation_with_dict`] but for the space-separated
+/// `word- word` split rather than a line-break-only split.
+///
+/// Runs the same suffix-table and pattern-engine heuristics, but when they
+/// have no opinion about a `before-after` split (the pattern engine found no
+/// syllable break, and the fragments are too long for the short-affix
+/// default to apply), asks `dict` whether the merged form or the two
+/// standalone parts are real words, and prefers whichever side the lexicon
+/// actually recognizes: merges `"detec-tion"` because `"detection"` is a
+/// word, but keeps `"retrieval-augmented"` hyphenated because `"retrieval"`
+/// and `"augmented"` are each words while `"retrievalaugmented"` isn't.
+///
+/// Takes the lexicon directly rather than through [`ParsingConfig`], since
+/// callers typically already hold a shared dictionary (e.g.
+/// `hallucinator_scowl`'s embedded SCOWL word list, or a domain-specific one
+/// for corpora like crypto or bio where the general heuristics fare worse)
+/// rather than wanting it rebuilt from config on every call.
+pub fn fix_hyphenation_with_lexicon(text: &str, config: &ParsingConfig, dict: &dyn Dictionary) -> String {
+    fix_hyphenation_inner(text, config, None, Some(dict), Language::English)
+}
+
+/// Exception-aware version of [`fix_hyphenation_with_config`].
+///
+/// Before any suffix table or pattern-engine heuristic runs, checks
+/// `exceptions` for the observed `before`/`after` split and uses its pinned
+/// result verbatim when present — see [`HyphenationExceptions`].
+pub fn fix_hyphenation_with_exceptions(
+    text: &str,
+    config: &ParsingConfig,
+    exceptions: &HyphenationExceptions,
+) -> String {
+    fix_hyphenation_inner(text, config, Some(exceptions), None, Language::English)
+}
+
+fn fix_hyphenation_inner(
+    text: &str,
+    config: &ParsingConfig,
+    exceptions: Option<&HyphenationExceptions>,
+    dict: Option<&dyn Dictionary>,
+    language: Language,
+) -> String {
     static RE: Lazy<Regex> = Lazy::new(|| {
         // Match: word chars, hyphen, whitespace (including newlines), then word chars
         // Changed to capture FULL word before hyphen for length-based heuristic
@@ -157,57 +407,91 @@ pub(crate) fn fix_hyphenation_with_config(text: &str, config: &ParsingConfig) ->
             let after_word = &caps[2];
             let after_lower = after_word.to_lowercase();
 
+            // User-pinned exceptions take priority over every other check.
+            if let Some(result) = exceptions.and_then(|e| e.lookup(before_word, after_word)) {
+                return result.to_string();
+            }
+
             // If the word before ends with a digit, keep the hyphen
             // (product/model names like "Qwen2-VL", "GPT-4-turbo")
             if before_word.chars().last().is_some_and(|c| c.is_ascii_digit()) {
                 return format!("{}-{}", before_word, after_word);
             }
 
-            // Check if the word after the hyphen is a common compound suffix
-            for suffix in suffix_set.iter() {
-                if after_lower == *suffix
-                    || after_lower.starts_with(&format!("{} ", suffix))
-                    || after_lower.starts_with(&format!("{},", suffix))
-                {
+            // The suffix table, connector-word list, and pattern engine
+            // below are all English-specific; other languages don't have
+            // their own populated versions yet, so they skip straight to
+            // the lexicon/short-affix fallback below.
+            if language == Language::English {
+                // Check if the word after the hyphen is a common compound suffix
+                for suffix in suffix_set.iter() {
+                    if after_lower == *suffix
+                        || after_lower.starts_with(&format!("{} ", suffix))
+                        || after_lower.starts_with(&format!("{},", suffix))
+                    {
+                        return format!("{}-{}", before_word, after_word);
+                    }
+                }
+
+                // Check if the full word (stripped of trailing punctuation) matches a suffix
+                let stripped = after_lower.trim_end_matches(['.', ',', ';', ':']);
+                if suffix_set.contains(stripped) {
                     return format!("{}-{}", before_word, after_word);
                 }
-            }
 
-            // Check if the full word (stripped of trailing punctuation) matches a suffix
-            let stripped = after_lower.trim_end_matches(['.', ',', ';', ':']);
-            if suffix_set.contains(stripped) {
-                return format!("{}-{}", before_word, after_word);
+                // If the word after the hyphen is a small connector word starting with uppercase,
+                // it's likely a compound proper noun (e.g., "Over-The-Air", "Up-To-Date").
+                static HYPHEN_CONNECTORS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+                    [
+                        "The", "To", "Of", "In", "On", "Up", "Out", "At", "By", "For", "And", "Or",
+                        "A", "An",
+                    ]
+                    .into_iter()
+                    .collect()
+                });
+                if HYPHEN_CONNECTORS.contains(after_word) {
+                    return format!("{}-{}", before_word, after_word);
+                }
             }
 
-            // If the word after the hyphen is a small connector word starting with uppercase,
-            // it's likely a compound proper noun (e.g., "Over-The-Air", "Up-To-Date").
-            static HYPHEN_CONNECTORS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
-                [
-                    "The", "To", "Of", "In", "On", "Up", "Out", "At", "By", "For", "And", "Or",
-                    "A", "An",
-                ]
-                .into_iter()
-                .collect()
-            });
-            if HYPHEN_CONNECTORS.contains(after_word) {
-                return format!("{}-{}", before_word, after_word);
+            // Run the Liang/TeX hyphenation-pattern engine on the merged word:
+            // if the hyphen fell on a real syllable boundary (e.g. "detec-
+            // tion" → "detection" has a legal break right where the hyphen
+            // was), it's a genuine line-break artifact — merge. If it didn't
+            // (e.g. "data-driven" → "datadriven" has no legal break there),
+            // it's a compound word — keep the hyphen. `DEFAULT_PATTERNS` is
+            // English-only, so other languages never get a match here.
+            let merged = format!("{}{}", before_word, after_word);
+            let gap = before_word.chars().count();
+            if language == Language::English && legal_break_points(&merged).contains(&gap) {
+                return merged;
             }
 
-            // HEURISTIC: If both parts are ≥4 letters and the second part is NOT a
-            // common syllable suffix, it's likely a compound word — keep the hyphen.
-            // This catches academic terms like "retrieval-augmented", "two-party", etc.
-            let before_alpha_len = before_word.chars().filter(|c| c.is_alphabetic()).count();
-            let after_alpha_len = after_word.chars().filter(|c| c.is_alphabetic()).count();
-
-            if before_alpha_len >= 4 && after_alpha_len >= 4 {
-                // Check if after_word looks like a syllable suffix (would indicate merge)
-                if !SYLLABLE_SUFFIXES.contains(stripped) {
+            // No pattern evidence either way — genuinely ambiguous. If a
+            // lexicon was supplied, let it settle the question: prefer
+            // whichever of the merged form or the two standalone parts it
+            // actually recognizes as real words.
+            if let Some(dict) = dict {
+                let merged_known = dict.contains(&merged);
+                let parts_known = dict.contains(before_word) && dict.contains(after_word);
+                if merged_known && !parts_known {
+                    return merged;
+                }
+                if parts_known && !merged_known {
                     return format!("{}-{}", before_word, after_word);
                 }
             }
 
-            // Otherwise, it's likely a syllable break — remove hyphen
-            format!("{}{}", before_word, after_word)
+            // Still no evidence either way. Very short fragments on either
+            // side (e.g. "re-", "pre-", "-fix", "-set") are almost always
+            // affixes rather than standalone compound roots, so default to
+            // merging; anything longer is treated as a genuine compound.
+            let before_alpha_len = before_word.chars().filter(|c| c.is_alphabetic()).count();
+            let after_alpha_len = after_word.chars().filter(|c| c.is_alphabetic()).count();
+            if before_alpha_len < 4 || after_alpha_len < 4 {
+                return merged;
+            }
+            format!("{}-{}", before_word, after_word)
         })
         .into_owned();
 
@@ -216,6 +500,68 @@ pub(crate) fn fix_hyphenation_with_config(text: &str, config: &ParsingConfig) ->
     RE_NO_SPACE.replace_all(&result, "$1$2$3").into_owned()
 }
 
+/// Line-break hyphenation pattern: a word fragment, a hyphen, optional
+/// inline whitespace, a line break, optional leading whitespace on the next
+/// line, then the continuation in lowercase.
+static LINE_BREAK_HYPHEN_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"([A-Za-z]{2,})-[ \t]*\n[ \t]*([a-z]+)").unwrap());
+
+/// Dictionary-driven de-hyphenation for words split across a PDF line break.
+///
+/// For each `word-\nword` span, asks `dict` whether the merged form (`L` +
+/// `R`) or the hyphenated compound (`L-R`) is the real dictionary word:
+///
+/// - If the merge is a dictionary word and the compound isn't, it's a
+///   line-break split — emit the merge (`"mod-\nels"` → `"models"`).
+/// - If both halves are standalone dictionary words and the merge isn't,
+///   it's a genuine compound (`"state-\nof-the-art"`) — keep the hyphen,
+///   collapsing the line break to a single space instead of deleting it.
+/// - Otherwise it's ambiguous: prefer the merge only when the left part
+///   ends in a consonant-vowel pair, a weak signal that it was cut
+///   mid-syllable rather than at a natural compound boundary.
+///
+/// Run this before segmentation and title extraction so `refs_to_titles`
+/// no longer surfaces tokens like `"mod-els"` or `"opti-mization"`. Callers
+/// without a dictionary should use [`fix_hyphenation`] instead, which falls
+/// back to the syllable-suffix table.
+pub fn fix_hyphenation_with_dict(text: &str, dict: &dyn Dictionary) -> String {
+    LINE_BREAK_HYPHEN_RE
+        .replace_all(text, |caps: &regex::Captures| {
+            let left = &caps[1];
+            let right = &caps[2];
+            let merged = format!("{left}{right}");
+            let hyphenated = format!("{left}-{right}");
+
+            if dict.contains(&merged) && !dict.contains(&hyphenated) {
+                return merged;
+            }
+
+            if dict.contains(left) && dict.contains(right) && !dict.contains(&merged) {
+                return format!("{left}- {right}");
+            }
+
+            if ends_in_consonant_vowel(left) {
+                merged
+            } else {
+                format!("{left}- {right}")
+            }
+        })
+        .into_owned()
+}
+
+/// Weak signal that `word` was cut mid-syllable: its last two letters are a
+/// consonant followed by a vowel (e.g. the "-ta" in "compu-tation").
+fn ends_in_consonant_vowel(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < 2 {
+        return false;
+    }
+    let is_vowel = |c: char| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u');
+    let last = chars[chars.len() - 1];
+    let second_last = chars[chars.len() - 2];
+    !is_vowel(second_last) && second_last.is_alphabetic() && is_vowel(last)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,6 +775,32 @@ mod tests {
         assert_eq!(fix_hyphenation("veri- fication"), "verification");
     }
 
+    // ── Liang/TeX pattern-engine tests ──
+
+    #[test]
+    fn test_legal_break_points_matches_syllable_suffix() {
+        // "detection" has a real break right before "tion".
+        assert!(legal_break_points("detection").contains(&5));
+        // "datadriven" has no pattern evidence of a break before "driven".
+        assert!(!legal_break_points("datadriven").contains(&4));
+    }
+
+    #[test]
+    fn test_legal_break_points_respects_min_two_letters() {
+        // "ok" is too short for any break point to satisfy the min-2 rule.
+        assert!(legal_break_points("ok").is_empty());
+    }
+
+    #[test]
+    fn test_legal_break_points_only_fires_at_the_right_offset() {
+        // "verification" has a real break right before "fication" (offset 4,
+        // matching where "veri-fication" was actually hyphenated), but no
+        // break one letter later.
+        let points = legal_break_points("verification");
+        assert!(points.contains(&4));
+        assert!(!points.contains(&5));
+    }
+
     #[test]
     fn test_fix_hyphenation_mixed_real_titles() {
         // Real academic paper titles with mixed hyphenation
@@ -455,4 +827,220 @@ mod tests {
         assert_eq!(fix_hyphenation("real- time"), "real-time"); // "time" is in suffixes
         assert_eq!(fix_hyphenation("zero- shot"), "zero-shot"); // "shot" is in suffixes
     }
+
+    // ── Dictionary-driven de-hyphenation tests ──
+
+    struct TestDict(HashSet<&'static str>);
+
+    impl Dictionary for TestDict {
+        fn contains(&self, word: &str) -> bool {
+            self.0.contains(word.to_lowercase().as_str())
+        }
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_dict_merges_line_break_split() {
+        let dict = TestDict(["models", "mod", "els"].into_iter().collect());
+        assert_eq!(
+            fix_hyphenation_with_dict("Language Mod-\nels.", &dict),
+            "Language Models."
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_dict_keeps_genuine_compound() {
+        let dict = TestDict(["human", "centered"].into_iter().collect());
+        assert_eq!(
+            fix_hyphenation_with_dict("a human-\ncentered approach", &dict),
+            "a human- centered approach"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_dict_ambiguous_prefers_consonant_vowel_merge() {
+        // "compu" ends in consonant+vowel ("pu"), so the ambiguous case
+        // should prefer merging into "computation" over keeping the hyphen.
+        let dict = TestDict(HashSet::new());
+        assert_eq!(
+            fix_hyphenation_with_dict("compu-\ntation", &dict),
+            "computation"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_lexicon_prefers_known_merge() {
+        let dict = TestDict(["detection"].into_iter().collect());
+        assert_eq!(
+            fix_hyphenation_with_lexicon("detec- tion", &ParsingConfig::default(), &dict),
+            "detection"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_config_consults_attached_lexicon() {
+        use crate::ParsingConfigBuilder;
+        use std::sync::Arc;
+
+        let dict = TestDict(["detection"].into_iter().collect());
+        let config = ParsingConfigBuilder::new()
+            .with_lexicon(Arc::new(dict))
+            .build()
+            .unwrap();
+        assert_eq!(
+            fix_hyphenation_with_config("detec- tion", &config),
+            "detection"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_config_consults_attached_exceptions() {
+        use crate::ParsingConfigBuilder;
+
+        let config = ParsingConfigBuilder::new()
+            .add_hyphenation_exception("multi", "plication", "multiplication")
+            .build()
+            .unwrap();
+        assert_eq!(
+            fix_hyphenation_with_config("multi- plication", &config),
+            "multiplication"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_config_consults_attached_language() {
+        use crate::ParsingConfigBuilder;
+
+        // "zero-day" only keeps its hyphen via the English-only
+        // COMPOUND_SUFFIXES table; a config built with a non-English
+        // language should skip it just like `fix_hyphenation_with_language`
+        // does directly.
+        let config = ParsingConfigBuilder::new()
+            .language(Language::German)
+            .build()
+            .unwrap();
+        assert_eq!(fix_hyphenation_with_config("zero- day", &config), "zeroday");
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_lexicon_prefers_known_parts() {
+        let dict = TestDict(["astro", "physics"].into_iter().collect());
+        // Neither the pattern engine nor the suffix table has an opinion on
+        // "astro-physics", but the lexicon knows both halves and not the
+        // merged form, so the hyphen is kept.
+        assert_eq!(
+            fix_hyphenation_with_lexicon("astro- physics", &ParsingConfig::default(), &dict),
+            "astro-physics"
+        );
+    }
+
+    // ── Language-parameterized tests ──
+
+    #[test]
+    fn test_expand_ligatures_for_language_german_long_s() {
+        assert_eq!(
+            expand_ligatures_for_language("Gesch\u{017F}\u{017F}pun-\u{017F}tion", Language::German),
+            "Geschsspun-stion"
+        );
+        // English is unaffected by the German-only substitution.
+        assert_eq!(
+            expand_ligatures_for_language("no ligatures here", Language::English),
+            "no ligatures here"
+        );
+    }
+
+    #[test]
+    fn test_expand_ligatures_for_language_dutch_ij() {
+        assert_eq!(
+            expand_ligatures_for_language("\u{0132}sselmeer en pr\u{0133}s", Language::Dutch),
+            "IJsselmeer en prijs"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_language_english_matches_default() {
+        assert_eq!(
+            fix_hyphenation_with_language("detec- tion", &ParsingConfig::default(), Language::English),
+            "detection"
+        );
+        assert_eq!(
+            fix_hyphenation_with_language("human- centered", &ParsingConfig::default(), Language::English),
+            "human-centered"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_language_non_english_skips_english_only_tables() {
+        // "zero-day" only keeps its hyphen via the English COMPOUND_SUFFIXES
+        // table ("day"); non-English languages don't consult it yet, so the
+        // short-affix fallback takes over and merges instead.
+        assert_eq!(
+            fix_hyphenation_with_language("zero- day", &ParsingConfig::default(), Language::English),
+            "zero-day"
+        );
+        assert_eq!(
+            fix_hyphenation_with_language("zero- day", &ParsingConfig::default(), Language::German),
+            "zeroday"
+        );
+    }
+
+    // ── Exception-table tests ──
+
+    #[test]
+    fn test_fix_hyphenation_with_exceptions_forces_merge() {
+        let mut exceptions = HyphenationExceptions::new();
+        exceptions.add_hyphenation_exception("multi", "plication", "multiplication");
+        assert_eq!(
+            fix_hyphenation_with_exceptions("multi- plication", &ParsingConfig::default(), &exceptions),
+            "multiplication"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_exceptions_forces_compound() {
+        let mut exceptions = HyphenationExceptions::new();
+        exceptions.add_hyphenation_exception("e", "commerce", "e-commerce");
+        assert_eq!(
+            fix_hyphenation_with_exceptions("e- commerce", &ParsingConfig::default(), &exceptions),
+            "e-commerce"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_exceptions_lookup_is_case_insensitive() {
+        let mut exceptions = HyphenationExceptions::new();
+        exceptions.add_hyphenation_exception("Co", "operate", "cooperate");
+        assert_eq!(
+            fix_hyphenation_with_exceptions("co- operate", &ParsingConfig::default(), &exceptions),
+            "cooperate"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_exceptions_set_replaces_table() {
+        // Without an exception, "auto-mobile" falls to the default
+        // keep-hyphen fallback (both parts are ≥4 letters with no pattern
+        // evidence). The exception table overrides that default.
+        assert_eq!(fix_hyphenation("auto- mobile"), "auto-mobile");
+
+        let exceptions = HyphenationExceptions::set_hyphenation_exceptions([(
+            "auto".to_string(),
+            "mobile".to_string(),
+            "automobile".to_string(),
+        )]);
+        assert_eq!(
+            fix_hyphenation_with_exceptions("auto- mobile", &ParsingConfig::default(), &exceptions),
+            "automobile"
+        );
+    }
+
+    #[test]
+    fn test_fix_hyphenation_with_dict_ambiguous_without_consonant_vowel_keeps_hyphen() {
+        // "fold" ends in consonant+consonant, not consonant+vowel, so with
+        // no dictionary evidence either way we keep the hyphenated form.
+        let dict = TestDict(HashSet::new());
+        assert_eq!(
+            fix_hyphenation_with_dict("k-fold-\ncross validation", &dict),
+            "k-fold- cross validation"
+        );
+    }
 }
@@ -11,7 +11,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use hallucinator_bbl::{extract_references_from_bbl_str, extract_references_from_bib_str};
-use hallucinator_core::matching::{normalize_title, titles_match};
+use hallucinator_core::matching::{TitleIndex, TitleMatcher, normalize_title, titles_match};
 use hallucinator_core::{BackendError, PdfBackend};
 use hallucinator_parsing::{ExtractionResult, Reference, ReferenceExtractor};
 use hallucinator_scowl::ScowlDictionary;
@@ -204,30 +204,6 @@ fn build_ground_truth(pair: &PaperPair) -> Option<GroundTruth> {
 // Matching
 // ---------------------------------------------------------------------------
 
-fn best_match_score(pdf_title: &str, gt_titles: &[String]) -> (Option<usize>, f64) {
-    let norm_pdf = normalize_title(pdf_title);
-    if norm_pdf.is_empty() {
-        return (None, 0.0);
-    }
-
-    let mut best_idx = None;
-    let mut best_score: f64 = 0.0;
-
-    for (i, gt) in gt_titles.iter().enumerate() {
-        let norm_gt = normalize_title(gt);
-        if norm_gt.is_empty() {
-            continue;
-        }
-        let score = rapidfuzz::fuzz::ratio(norm_pdf.chars(), norm_gt.chars());
-        if score > best_score {
-            best_score = score;
-            best_idx = Some(i);
-        }
-    }
-
-    (best_idx, best_score)
-}
-
 fn evaluate_paper(
     pdf_refs: &[Reference],
     gt: &GroundTruth,
@@ -237,6 +213,12 @@ fn evaluate_paper(
     let mut no_title = 0usize;
     let mut near_misses = Vec::new();
 
+    // Built once per paper instead of doing an O(pdf_refs × gt_titles) linear
+    // scan per reference: `TitleIndex` amortizes the tokenization of
+    // `gt.titles` across every unmatched reference in this paper.
+    let matcher = TitleMatcher::default();
+    let title_index = TitleIndex::build(&gt.titles);
+
     for pdf_ref in pdf_refs {
         if pdf_ref.skip_reason.is_some() {
             continue;
@@ -255,7 +237,7 @@ fn evaluate_paper(
         if is_match {
             matched += 1;
         } else {
-            let (best_idx, best_score) = best_match_score(title, &gt.titles);
+            let (best_idx, best_score) = title_index.best_match_with(&matcher, title);
             if (80.0..95.0).contains(&best_score)
                 && let Some(idx) = best_idx
             {
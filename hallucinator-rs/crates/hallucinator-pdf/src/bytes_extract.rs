@@ -0,0 +1,241 @@
+//! Byte-oriented reference extraction.
+//!
+//! [`crate::section::find_references_section`]'s 30% fallback cutoff has to
+//! hand-walk `char_indices` to avoid slicing mid-codepoint, and backends
+//! that emit Latin-1 or mixed-encoding text (common in older conference
+//! PDFs) already lose references to `\u{FFFD}` replacement characters
+//! before this module ever sees them, since the lossy UTF-8 conversion
+//! happens up front. This module mirrors `section`'s header/end-marker and
+//! segmentation patterns as `regex::bytes` patterns so detection runs
+//! directly on raw `&[u8]`, with UTF-8 decoding deferred to the very end —
+//! only matched reference spans are ever turned into a `String`.
+
+use once_cell::sync::Lazy;
+use regex::bytes::Regex as BytesRegex;
+
+/// Locate the references section in raw extracted bytes. Byte-oriented
+/// counterpart to [`crate::section::find_references_section`]: no
+/// codepoint-boundary bookkeeping is needed for the 30% fallback cutoff,
+/// and the input doesn't need to be valid UTF-8 up front.
+pub fn find_references_section_bytes(bytes: &[u8]) -> Option<Vec<u8>> {
+    static HEADER_RE: Lazy<BytesRegex> = Lazy::new(|| {
+        BytesRegex::new(r"(?i)\n\s*(?:References|Bibliography|Works\s+Cited)\s*\n").unwrap()
+    });
+
+    if let Some(m) = HEADER_RE.find(bytes) {
+        let ref_start = m.end();
+        let rest = &bytes[ref_start..];
+
+        static END_RE: Lazy<BytesRegex> = Lazy::new(|| {
+            BytesRegex::new(r"(?i)\n\s*(?:Appendix|Acknowledgments|Acknowledgements|Supplementary|Ethics\s+Statement|Ethical\s+Considerations|Broader\s+Impact|Paper\s+Checklist|Checklist)")
+                .unwrap()
+        });
+
+        let ref_end = END_RE.find(rest).map(|m| m.start()).unwrap_or(rest.len());
+        let section = &rest[..ref_end];
+        if !section.iter().all(u8::is_ascii_whitespace) {
+            return Some(section.to_vec());
+        }
+    }
+
+    // Fallback: last 30% of the document — a plain byte-index cutoff, since
+    // there's no `&str` here to worry about splitting mid-codepoint.
+    let cutoff = (bytes.len() as f64 * 0.7) as usize;
+    Some(bytes[cutoff.min(bytes.len())..].to_vec())
+}
+
+/// Split a byte-oriented references section into individual reference
+/// strings, decoded from their matched byte spans only at this final step.
+/// Mirrors [`crate::section::segment_references`]'s IEEE and numbered-list
+/// strategies (the two that depend purely on line-start markers); callers
+/// wanting the full regex cascade should decode to `&str` and use
+/// [`crate::section::segment_references`] directly.
+pub fn segment_references_bytes(ref_bytes: &[u8]) -> Vec<String> {
+    if let Some(refs) = try_ieee_bytes(ref_bytes) {
+        return refs;
+    }
+    if let Some(refs) = try_numbered_bytes(ref_bytes) {
+        return refs;
+    }
+    fallback_double_newline_bytes(ref_bytes)
+}
+
+fn try_ieee_bytes(ref_bytes: &[u8]) -> Option<Vec<String>> {
+    static RE: Lazy<BytesRegex> = Lazy::new(|| BytesRegex::new(r"\n\s*\[(\d+)\]\s*").unwrap());
+
+    let matches: Vec<_> = RE.find_iter(ref_bytes).collect();
+    if matches.len() < 3 {
+        return None;
+    }
+
+    let mut refs = Vec::new();
+    for i in 0..matches.len() {
+        let start = matches[i].end();
+        let end = matches
+            .get(i + 1)
+            .map(|m| m.start())
+            .unwrap_or(ref_bytes.len());
+        if let Some(s) = decode_trimmed(&ref_bytes[start..end]) {
+            refs.push(s);
+        }
+    }
+    Some(refs)
+}
+
+fn try_numbered_bytes(ref_bytes: &[u8]) -> Option<Vec<String>> {
+    static RE: Lazy<BytesRegex> =
+        Lazy::new(|| BytesRegex::new(r"(?m)(?:^|\n)\s*(\d+)\.\s+").unwrap());
+
+    let matches: Vec<_> = RE.find_iter(ref_bytes).collect();
+    if matches.len() < 3 {
+        return None;
+    }
+
+    let caps: Vec<_> = RE.captures_iter(ref_bytes).collect();
+    let first_nums: Vec<i64> = caps
+        .iter()
+        .take(5)
+        .filter_map(|c| c.get(1))
+        .filter_map(|m| std::str::from_utf8(m.as_bytes()).ok())
+        .filter_map(|s| s.parse().ok())
+        .collect();
+
+    if first_nums.is_empty() || first_nums[0] != 1 {
+        return None;
+    }
+    if !first_nums.windows(2).all(|w| w[1] == w[0] + 1) {
+        return None;
+    }
+
+    let mut refs = Vec::new();
+    for i in 0..matches.len() {
+        let start = matches[i].end();
+        let end = matches
+            .get(i + 1)
+            .map(|m| m.start())
+            .unwrap_or(ref_bytes.len());
+        if let Some(s) = decode_trimmed(&ref_bytes[start..end]) {
+            refs.push(s);
+        }
+    }
+    Some(refs)
+}
+
+fn fallback_double_newline_bytes(ref_bytes: &[u8]) -> Vec<String> {
+    static RE: Lazy<BytesRegex> = Lazy::new(|| BytesRegex::new(r"\n\s*\n").unwrap());
+
+    RE.split(ref_bytes)
+        .filter_map(decode_trimmed)
+        .filter(|s| s.len() > 20)
+        .collect()
+}
+
+/// Byte-oriented reference extraction as a single call, bundling
+/// [`find_references_section_bytes`] and [`segment_references_bytes`]
+/// together for callers who don't need the two steps separately.
+///
+/// There's no default extraction pipeline in this crate for this to be
+/// wired into: `hallucinator-cli` calls `hallucinator_pdf::extract_references`,
+/// but that function doesn't exist anywhere in this crate's source (see the
+/// crate-level docs), so this extractor currently has no caller beyond its
+/// own tests — constructing one and calling [`ByteReferenceExtractor::extract`]
+/// is how a future pipeline would reach it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ByteReferenceExtractor;
+
+impl ByteReferenceExtractor {
+    /// Use the built-in byte-oriented header/end-marker and segmentation
+    /// patterns.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Find the references section in `bytes`, then segment it. Mirrors
+    /// `find_references_section_bytes(bytes).map(|s|
+    /// segment_references_bytes(&s)).unwrap_or_default()`, since
+    /// `find_references_section_bytes`'s 30% fallback cutoff means it only
+    /// returns `None` for empty input.
+    pub fn extract(&self, bytes: &[u8]) -> Vec<String> {
+        find_references_section_bytes(bytes)
+            .map(|section| segment_references_bytes(&section))
+            .unwrap_or_default()
+    }
+}
+
+/// Decode a matched byte span to `String` at the very last step, lossily —
+/// a stray non-UTF-8 byte from mixed-encoding PDF text degrades to a
+/// `\u{FFFD}` replacement character instead of the whole reference vanishing
+/// the way it would if the source text had been lossily decoded up front.
+fn decode_trimmed(bytes: &[u8]) -> Option<String> {
+    let decoded = String::from_utf8_lossy(bytes).trim().to_string();
+    if decoded.is_empty() {
+        None
+    } else {
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_references_section_bytes_basic() {
+        let text = b"Some content here.\n\nReferences\n\n[1] First ref.\n[2] Second ref.\n";
+        let section = find_references_section_bytes(text).unwrap();
+        let section = String::from_utf8(section).unwrap();
+        assert!(section.contains("[1] First ref."));
+    }
+
+    #[test]
+    fn test_find_references_section_bytes_with_appendix() {
+        let text = b"Body.\n\nReferences\n\n[1] Ref one.\n\nAppendix A\n\nExtra stuff.";
+        let section = find_references_section_bytes(text).unwrap();
+        let section = String::from_utf8(section).unwrap();
+        assert!(section.contains("[1] Ref one."));
+        assert!(!section.contains("Extra stuff"));
+    }
+
+    #[test]
+    fn test_segment_bytes_ieee() {
+        let text = b"\n[1] First reference text here.\n[2] Second reference text here.\n[3] Third reference.\n";
+        let refs = segment_references_bytes(text);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].starts_with("First"));
+    }
+
+    #[test]
+    fn test_segment_bytes_numbered() {
+        let text = b"1. First ref content here that is long enough.\n2. Second ref content here that is long enough.\n3. Third ref content.\n";
+        let refs = segment_references_bytes(text);
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_segment_bytes_survives_invalid_utf8() {
+        // 0xE9 alone (Latin-1 "é") is not valid UTF-8 — a naive lossy decode
+        // up front would have already mangled or dropped this reference.
+        let mut text = b"\n[1] Caf\xe9 study on espresso extraction methods.\n".to_vec();
+        text.extend_from_slice(b"[2] Second reference text here that is long enough.\n");
+        text.extend_from_slice(b"[3] Third reference text here that is long enough.\n");
+
+        let refs = segment_references_bytes(&text);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].contains('\u{FFFD}'), "expected replacement char, got {:?}", refs[0]);
+    }
+
+    #[test]
+    fn test_byte_reference_extractor_runs_the_full_pipeline() {
+        let text = b"Some content here.\n\nReferences\n\n[1] First ref.\n[2] Second ref.\n[3] Third ref.\n";
+        let refs = ByteReferenceExtractor::new().extract(text);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].starts_with("First"));
+    }
+
+    #[test]
+    fn test_find_references_section_bytes_fallback() {
+        let text = b"No header at all, just a wall of text that should trigger the 30% fallback cutoff because nothing else matched anywhere in this document body.";
+        let section = find_references_section_bytes(text).unwrap();
+        assert!(!section.is_empty());
+    }
+}
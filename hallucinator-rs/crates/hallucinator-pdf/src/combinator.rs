@@ -0,0 +1,284 @@
+//! Parser-combinator based reference segmentation.
+//!
+//! [`segment_references`](crate::section::segment_references) picks the
+//! first regex heuristic that clears its threshold, which can misfire —
+//! e.g. a numbered list whose entries themselves contain inline `[n]`
+//! citations looks like an IEEE list to the bracketed strategy, since regex
+//! can't backtrack across interleaved markers. This module instead
+//! tokenizes the reference block into a stream of line-level tokens, runs
+//! each marker grammar (bracketed, numbered, author-year) as a composable
+//! parser over that same stream, and keeps whichever one consumes the most
+//! input with the fewest unclaimed gaps.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// One segmented reference plus how confident the parse is in the overall
+/// split (shared across all entries of a single winning grammar run).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reference {
+    pub text: String,
+    pub confidence: f32,
+}
+
+/// A line-level token in the reference block, tagged with its byte offset
+/// into the original text so matched spans can be sliced back out directly.
+#[derive(Debug, Clone)]
+enum Token<'a> {
+    /// A line that opens a new entry, tagged with every marker grammar it
+    /// satisfies (a line can match more than one, e.g. `"12. Smith, J."`).
+    Marker {
+        line: &'a str,
+        start: usize,
+        kinds: Vec<MarkerKind>,
+    },
+    /// A blank line — a potential "unclaimed gap" if it falls between
+    /// entries that a grammar recognized.
+    Blank { start: usize },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MarkerKind {
+    Bracketed(u32),
+    Numbered(u32),
+    AuthorYear,
+}
+
+static BRACKET_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*\[(\d+)\]\s*").unwrap());
+static NUMBERED_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(\d+)\.\s+").unwrap());
+static AUTHOR_YEAR_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*[A-Z][\p{L}\-]+,\s+[A-Z]\.").unwrap());
+static TRAILING_PAGENUM_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n+\d+\s*$").unwrap());
+
+/// Tokenize `text` into line-level tokens (marker lines vs. blank lines;
+/// everything else is implicit entry body and doesn't need its own token).
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut pos = 0usize;
+    for line in text.split('\n') {
+        if line.trim().is_empty() {
+            tokens.push(Token::Blank { start: pos });
+        } else {
+            let mut kinds = Vec::new();
+            if let Some(c) = BRACKET_RE.captures(line) {
+                if let Ok(n) = c[1].parse() {
+                    kinds.push(MarkerKind::Bracketed(n));
+                }
+            }
+            if let Some(c) = NUMBERED_RE.captures(line) {
+                if let Ok(n) = c[1].parse() {
+                    kinds.push(MarkerKind::Numbered(n));
+                }
+            }
+            if AUTHOR_YEAR_RE.is_match(line) {
+                kinds.push(MarkerKind::AuthorYear);
+            }
+            if !kinds.is_empty() {
+                tokens.push(Token::Marker { line, start: pos, kinds });
+            }
+        }
+        pos += line.len() + 1; // +1 for the '\n' consumed by split
+    }
+    tokens
+}
+
+/// Which grammar family a token's marker kinds are checked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grammar {
+    Bracketed,
+    Numbered,
+    AuthorYear,
+}
+
+impl Grammar {
+    fn number_of(self, kinds: &[MarkerKind]) -> Option<Option<u32>> {
+        kinds.iter().find_map(|k| match (self, k) {
+            (Grammar::Bracketed, MarkerKind::Bracketed(n)) => Some(Some(*n)),
+            (Grammar::Numbered, MarkerKind::Numbered(n)) => Some(Some(*n)),
+            (Grammar::AuthorYear, MarkerKind::AuthorYear) => Some(None),
+            _ => None,
+        })
+    }
+}
+
+/// Result of running one grammar over the token stream: the entries it
+/// found, how much of the input it consumed from its first marker onward,
+/// and how many blank-line gaps fell between recognized entries (gaps
+/// penalize the parse when picking a winner — a clean list has none).
+struct GrammarParse {
+    entries: Vec<Reference>,
+    consumed: usize,
+    gaps: usize,
+}
+
+/// `marker` + `entry = marker ~ text.until(next_marker)` + `reference_list =
+/// entry.repeat(3..)`, rolled into a single pass: collect every token whose
+/// line opens an entry under `grammar`, require the numeric grammars to
+/// start at 1 and run strictly sequentially (the same invariant
+/// [`crate::section`]'s `try_numbered` already enforces, so scattered
+/// inline `[n]` citations can't masquerade as a bracketed reference list),
+/// then treat the span up to the next marker (or end of input) as that
+/// entry's body.
+fn run_grammar(tokens: &[Token], grammar: Grammar, full_text: &str) -> Option<GrammarParse> {
+    let boundaries: Vec<(usize, usize, Option<u32>)> = tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, tok)| match tok {
+            Token::Marker { start, kinds, .. } => {
+                grammar.number_of(kinds).map(|n| (i, *start, n))
+            }
+            Token::Blank { .. } => None,
+        })
+        .collect();
+
+    if boundaries.len() < 3 {
+        return None;
+    }
+
+    if matches!(grammar, Grammar::Bracketed | Grammar::Numbered) {
+        let nums: Vec<u32> = boundaries.iter().filter_map(|b| b.2).collect();
+        if nums.first() != Some(&1) || !nums.windows(2).all(|w| w[1] == w[0] + 1) {
+            return None;
+        }
+    }
+
+    let mut entries = Vec::new();
+    let mut gaps = 0usize;
+
+    for (idx, &(token_idx, byte_start, _)) in boundaries.iter().enumerate() {
+        let marker_line_end = match &tokens[token_idx] {
+            Token::Marker { line, .. } => byte_start + line.len(),
+            Token::Blank { .. } => byte_start,
+        };
+        let body_end = boundaries.get(idx + 1).map(|b| b.1).unwrap_or(full_text.len());
+
+        let body = TRAILING_PAGENUM_RE.replace(
+            &full_text[marker_line_end.min(full_text.len())..body_end.min(full_text.len())],
+            "",
+        );
+        let body = body.trim();
+        if !body.is_empty() {
+            entries.push(Reference {
+                text: body.to_string(),
+                confidence: 1.0, // filled in with the run's overall confidence below
+            });
+        }
+
+        if let Some(&(next_token_idx, ..)) = boundaries.get(idx + 1) {
+            gaps += tokens[token_idx + 1..next_token_idx]
+                .iter()
+                .filter(|t| matches!(t, Token::Blank { .. }))
+                .count();
+        }
+    }
+
+    // Author-year markers sit at the *end* of the previous entry ("Smith,
+    // J." begins the next one), so the text before the very first marker is
+    // itself the leading entry — the same edge case `try_aaai` preserves.
+    if grammar == Grammar::AuthorYear {
+        let leading = full_text[..boundaries[0].1].trim();
+        if leading.len() > 20 {
+            entries.insert(
+                0,
+                Reference {
+                    text: leading.to_string(),
+                    confidence: 1.0,
+                },
+            );
+        }
+    }
+
+    if entries.len() < 3 {
+        return None;
+    }
+
+    Some(GrammarParse {
+        consumed: full_text.len() - boundaries[0].1,
+        entries,
+        gaps,
+    })
+}
+
+/// Grammar-driven reference segmentation: tokenize `ref_text` and run the
+/// bracketed, numbered, and author-year grammars over the same token
+/// stream, keeping whichever consumes the most input with the fewest
+/// unclaimed gaps. Intended as an alternative segmentation strategy to the
+/// regex cascade in [`crate::section::segment_references`], not a
+/// replacement for it — callers that want the fast path should try that one
+/// first and fall back here.
+pub fn segment_references_parsed(ref_text: &str) -> Vec<Reference> {
+    let tokens = tokenize(ref_text);
+
+    let best = [Grammar::Numbered, Grammar::Bracketed, Grammar::AuthorYear]
+        .into_iter()
+        .filter_map(|g| run_grammar(&tokens, g, ref_text))
+        .max_by_key(|p| p.consumed as i64 - p.gaps as i64 * 200);
+
+    let Some(best) = best else {
+        return Vec::new();
+    };
+
+    let confidence = (1.0 - best.gaps as f32 * 0.1).clamp(0.1, 1.0);
+    best.entries
+        .into_iter()
+        .map(|r| Reference { confidence, ..r })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_parsed_ieee() {
+        let text = "\n[1] First reference text here.\n[2] Second reference text here.\n[3] Third reference.\n";
+        let refs = segment_references_parsed(text);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].text.starts_with("First"));
+        assert!(refs[0].confidence > 0.9);
+    }
+
+    #[test]
+    fn test_segment_parsed_numbered_with_inline_brackets() {
+        // Entries themselves contain [n]-style inline citations, which
+        // would confuse a naive regex cascade into misreading this as an
+        // IEEE list keyed on those inline markers instead.
+        let text = "1. Smith et al. build on [3] and [7] to improve accuracy.\n\
+                    2. Jones extends [1] with a new loss term entirely.\n\
+                    3. Lee proposes a variant that cites [2] and [4] directly.\n";
+        let refs = segment_references_parsed(text);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].text.starts_with("Smith"));
+        assert!(refs[1].text.starts_with("Jones"));
+        assert!(refs[2].text.starts_with("Lee"));
+    }
+
+    #[test]
+    fn test_segment_parsed_author_year_keeps_leading_entry() {
+        let text = "Alpha, B. Some early result from nineteen ninety.\n\
+                    Bravo, C. A follow-up study with more data.\n\
+                    Charlie, D. A third independent replication.\n\
+                    Delta, E. A fourth paper closing the loop.\n";
+        let refs = segment_references_parsed(text);
+        assert_eq!(refs.len(), 4);
+        assert!(refs[0].text.starts_with("Alpha"));
+    }
+
+    #[test]
+    fn test_segment_parsed_rejects_non_sequential_numbers() {
+        // Numbers present but not starting at 1 / not sequential — should
+        // not be accepted as a numbered-list grammar match.
+        let text = "5. Out of order first entry here that is long enough.\n\
+                    12. Another out of order entry that is long enough.\n\
+                    3. Yet another out of order entry long enough.\n";
+        let refs = segment_references_parsed(text);
+        assert!(refs.is_empty());
+    }
+
+    #[test]
+    fn test_segment_parsed_too_few_entries_returns_empty() {
+        let text = "[1] Only one reference here.\n[2] And a second one.\n";
+        let refs = segment_references_parsed(text);
+        assert!(refs.is_empty());
+    }
+}
@@ -0,0 +1,15 @@
+//! Reference-section detection and segmentation over text extracted from
+//! PDFs: [`section`] (string-oriented), [`bytes_extract`] (byte-oriented,
+//! for mixed/unknown-encoding extraction output), [`ruleset`]
+//! (user-supplied citation-marker patterns), and [`combinator`] (structured
+//! `Reference` parsing).
+//!
+//! `hallucinator-cli` calls `hallucinator_pdf::extract_references`, but no
+//! such function (or the `Extraction`/`skip_stats` types it'd need to
+//! return) exists anywhere in this crate's source — that's a pre-existing
+//! gap in this tree, not something any one module here can wire itself
+//! into.
+pub mod bytes_extract;
+pub mod combinator;
+pub mod ruleset;
+pub mod section;
@@ -0,0 +1,191 @@
+//! User-supplied citation-style patterns, compiled from a pattern file.
+//!
+//! Each non-empty, non-comment line in a pattern file is a citation-marker
+//! template such as `[{n}]`, `{n}.`, `({surname}, {year})`, or
+//! `{surname}, {initial}.`. Templates are compiled into anchored regexes the
+//! way Mercurial compiles glob patterns to regexes: every literal character
+//! that's a regex metacharacter is escaped via a fixed lookup table, then
+//! placeholder tokens are substituted in a fixed order and the result is
+//! anchored to a line/entry start. This lets users describe regional
+//! citation styles (Chicago, Harvard variants, journal-specific formats)
+//! declaratively instead of patching a new `try_*` function into
+//! [`crate::section`].
+
+use regex::Regex;
+
+/// Placeholder substitutions, applied in a fixed order while compiling a
+/// template so one placeholder's replacement can't be mistaken for another.
+const PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{n}", r"(\d+)"),
+    ("{year}", r"((?:19|20)\d{2}[a-z]?)"),
+    ("{surname}", r"[A-Z][\p{L}\-]+"),
+    ("{initial}", r"[A-Z]\."),
+];
+
+/// Regex metacharacters escaped one character at a time while compiling a
+/// template, so a template can contain them as literal punctuation.
+const METACHARS: &str = r"()[]{}?*+-|^$.\";
+
+/// One compiled marker template, anchored to a line/entry start.
+struct CompiledRule {
+    regex: Regex,
+}
+
+/// A set of user-supplied citation-marker templates, compiled at load time.
+pub struct SegmentationRuleset {
+    rules: Vec<CompiledRule>,
+}
+
+impl SegmentationRuleset {
+    /// Parse a pattern file: one template per non-empty, non-`#`-comment
+    /// line. A template that fails to compile (e.g. an unanchorable empty
+    /// pattern) is skipped rather than rejecting the whole file.
+    pub fn from_str(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(compile_rule)
+            .collect();
+        Self { rules }
+    }
+
+    /// Load a pattern file from disk. See [`Self::from_str`] for the format.
+    pub fn from_file(path: &std::path::Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::from_str(&content))
+    }
+
+    /// True if no template in the file compiled (or the file was empty) —
+    /// callers should fall back to the built-in strategies in this case.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Run each compiled rule over `ref_text` in file order, using the same
+    /// "need ≥3 boundary matches to accept" gate the built-in strategies
+    /// use. Returns the first rule's segmentation to clear that bar.
+    pub(crate) fn segment(&self, ref_text: &str) -> Option<Vec<String>> {
+        for rule in &self.rules {
+            let matches: Vec<_> = rule.regex.find_iter(ref_text).collect();
+            if matches.len() < 3 {
+                continue;
+            }
+
+            let refs: Vec<String> = (0..matches.len())
+                .map(|i| {
+                    let start = matches[i].end();
+                    let end = matches
+                        .get(i + 1)
+                        .map(|m| m.start())
+                        .unwrap_or(ref_text.len());
+                    ref_text[start..end].trim().to_string()
+                })
+                .filter(|s| !s.is_empty())
+                .collect();
+
+            if refs.len() >= 3 {
+                return Some(refs);
+            }
+        }
+        None
+    }
+}
+
+fn compile_rule(template: &str) -> Option<CompiledRule> {
+    let pattern = compile_template_to_regex(template)?;
+    Regex::new(&pattern).ok().map(|regex| CompiledRule { regex })
+}
+
+/// Compile one marker template into an anchored regex: walk the template
+/// character by character, splicing in a placeholder's regex whenever one
+/// matches at the current position, and otherwise escaping the literal
+/// character if it's a regex metacharacter.
+fn compile_template_to_regex(template: &str) -> Option<String> {
+    if template.is_empty() {
+        return None;
+    }
+
+    let mut body = String::new();
+    let mut rest = template;
+    'outer: while !rest.is_empty() {
+        for (token, replacement) in PLACEHOLDERS {
+            if let Some(stripped) = rest.strip_prefix(token) {
+                body.push_str(replacement);
+                rest = stripped;
+                continue 'outer;
+            }
+        }
+        let ch = rest.chars().next().unwrap();
+        if METACHARS.contains(ch) {
+            body.push('\\');
+        }
+        body.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    Some(format!(r"(?m)(?:^|\n)\s*{body}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compiles_bracketed_template() {
+        let ruleset = SegmentationRuleset::from_str("[{n}]");
+        assert!(!ruleset.is_empty());
+
+        let text = "\n[1] First reference.\n[2] Second reference.\n[3] Third reference.\n";
+        let refs = ruleset.segment(text).unwrap();
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0], "First reference.");
+    }
+
+    #[test]
+    fn test_compiles_numbered_template() {
+        let ruleset = SegmentationRuleset::from_str("{n}.");
+        let text = "1. First ref.\n2. Second ref.\n3. Third ref.\n";
+        let refs = ruleset.segment(text).unwrap();
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_compiles_author_year_template() {
+        let ruleset = SegmentationRuleset::from_str("{surname}, {initial}. ({year})");
+        let text = "Smith, J. (2020) Some paper title here.\n\
+                    Jones, A. (2019) Another paper title here.\n\
+                    Lee, C. (2021) A third paper title here.\n";
+        let refs = ruleset.segment(text).unwrap();
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_ignores_comments_and_blank_lines() {
+        let ruleset = SegmentationRuleset::from_str("# a comment\n\n[{n}]\n");
+        assert!(!ruleset.is_empty());
+    }
+
+    #[test]
+    fn test_below_threshold_returns_none() {
+        let ruleset = SegmentationRuleset::from_str("[{n}]");
+        let text = "[1] Only one.\n[2] Only two.\n";
+        assert!(ruleset.segment(text).is_none());
+    }
+
+    #[test]
+    fn test_falls_through_to_next_rule() {
+        // First template never matches three times; second one should win.
+        let ruleset = SegmentationRuleset::from_str("({year})\n[{n}]");
+        let text = "\n[1] First reference.\n[2] Second reference.\n[3] Third reference.\n";
+        let refs = ruleset.segment(text).unwrap();
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_empty_file_yields_empty_ruleset() {
+        let ruleset = SegmentationRuleset::from_str("");
+        assert!(ruleset.is_empty());
+        assert!(ruleset.segment("[1] a\n[2] b\n[3] c\n").is_none());
+    }
+}
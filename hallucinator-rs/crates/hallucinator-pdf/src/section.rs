@@ -1,6 +1,8 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::ruleset::SegmentationRuleset;
+
 /// Locate the references section in the document text.
 ///
 /// Searches for common reference section headers (References, Bibliography, Works Cited)
@@ -76,6 +78,21 @@ pub fn segment_references(ref_text: &str) -> Vec<String> {
     fallback_double_newline(ref_text)
 }
 
+/// Like [`segment_references`], but first tries `ruleset`'s user-supplied
+/// marker templates as a high-priority strategy, using the same "need ≥3
+/// boundary matches to accept" gate the built-in strategies use, before
+/// falling back to the regex cascade. Lets users register custom citation
+/// styles at runtime without a new `try_*` function in this module.
+pub fn segment_references_with_rules(
+    ref_text: &str,
+    ruleset: &SegmentationRuleset,
+) -> Vec<String> {
+    if let Some(refs) = ruleset.segment(ref_text) {
+        return refs;
+    }
+    segment_references(ref_text)
+}
+
 fn try_ieee(ref_text: &str) -> Option<Vec<String>> {
     static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n\s*\[(\d+)\]\s*").unwrap());
 
@@ -271,6 +288,86 @@ fn try_springer_nature(ref_text: &str) -> Option<Vec<String>> {
     Some(refs)
 }
 
+/// Run every segmentation strategy and score the resulting split, instead
+/// of accepting whichever strategy happens to fire first. A strategy with
+/// fewer total boundary matches can still win if its segments are more
+/// uniform in length and more of them clear the 20-char minimum — signals
+/// the strategies already compute internally, just never compared against
+/// each other. Per-reference confidence is the winning split's score,
+/// penalized for segments that are suspiciously short or lack a detected
+/// year.
+///
+/// This doesn't yet feed `ExtractionResult` — that type lives in the
+/// extractor module this crate doesn't currently have visibility into —
+/// but a caller with access to it should attach these scores per-reference
+/// so low-confidence entries can be weighted or quarantined downstream
+/// instead of trusted equally to a clean, unambiguous split.
+pub fn segment_references_scored(ref_text: &str) -> Vec<(String, f32)> {
+    let candidates: Vec<Vec<String>> = [
+        try_ieee(ref_text),
+        try_numbered(ref_text),
+        try_aaai(ref_text),
+        try_springer_nature(ref_text),
+    ]
+    .into_iter()
+    .flatten()
+    .chain(std::iter::once(fallback_double_newline(ref_text)))
+    .filter(|refs| refs.len() >= 3)
+    .collect();
+
+    let Some(best) = candidates
+        .iter()
+        .max_by(|a, b| score_split(a).partial_cmp(&score_split(b)).unwrap())
+    else {
+        return Vec::new();
+    };
+
+    let split_score = score_split(best);
+    best.iter()
+        .map(|r| (r.clone(), per_reference_confidence(r, split_score)))
+        .collect()
+}
+
+/// Score a candidate segmentation: more boundaries, a higher fraction of
+/// segments clearing the 20-char minimum, and tighter length variance (real
+/// reference lists have fairly uniform entry lengths) all push the score up.
+fn score_split(refs: &[String]) -> f32 {
+    if refs.is_empty() {
+        return 0.0;
+    }
+
+    let lengths: Vec<f32> = refs.iter().map(|r| r.len() as f32).collect();
+    let mean = lengths.iter().sum::<f32>() / lengths.len() as f32;
+    let variance = lengths.iter().map(|l| (l - mean).powi(2)).sum::<f32>() / lengths.len() as f32;
+    // Coefficient of variation — scale-free, so a list of long references
+    // isn't penalized just for having a larger absolute spread than a list
+    // of short ones would.
+    let cv = if mean > 0.0 { variance.sqrt() / mean } else { 1.0 };
+
+    let long_enough = refs.iter().filter(|r| r.len() > 20).count() as f32 / refs.len() as f32;
+    let boundary_score = (refs.len() as f32).ln_1p();
+
+    boundary_score * 0.4 + long_enough * 0.4 + (1.0 / (1.0 + cv)) * 0.2
+}
+
+/// Per-reference confidence: the winning split's overall score, penalized
+/// for an entry that's suspiciously short or doesn't contain anything that
+/// looks like a publication year.
+fn per_reference_confidence(reference: &str, split_score: f32) -> f32 {
+    static YEAR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:19|20)\d{2}").unwrap());
+
+    let mut confidence = (split_score / 2.0).clamp(0.0, 1.0);
+
+    if reference.len() < 20 {
+        confidence *= 0.5;
+    }
+    if !YEAR_RE.is_match(reference) {
+        confidence *= 0.8;
+    }
+
+    confidence.clamp(0.0, 1.0)
+}
+
 fn fallback_double_newline(ref_text: &str) -> Vec<String> {
     static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\n\s*\n").unwrap());
 
@@ -331,4 +428,63 @@ mod tests {
         let section = find_references_section(text).unwrap();
         assert!(section.contains("Some refs here."));
     }
+
+    #[test]
+    fn test_segment_with_rules_prefers_custom_template() {
+        let ruleset = SegmentationRuleset::from_str("({year})");
+        let text = "Some paper (2020) that a custom journal style cites this way.\n\
+                    Another paper (2019) cited the same way.\n\
+                    A third paper (2021) also cited the same way.\n";
+        let refs = segment_references_with_rules(text, &ruleset);
+        assert_eq!(refs.len(), 3);
+    }
+
+    #[test]
+    fn test_segment_with_rules_falls_back_when_ruleset_empty() {
+        let ruleset = SegmentationRuleset::from_str("");
+        let text = "\n[1] First reference text here.\n[2] Second reference text here.\n[3] Third reference.\n";
+        let refs = segment_references_with_rules(text, &ruleset);
+        assert_eq!(refs.len(), 3);
+        assert!(refs[0].starts_with("First"));
+    }
+
+    #[test]
+    fn test_segment_scored_picks_clean_ieee_split() {
+        let text = "\n[1] First reference from 2019 here.\n[2] Second reference from 2020 here.\n[3] Third reference from 2021 here.\n";
+        let scored = segment_references_scored(text);
+        assert_eq!(scored.len(), 3);
+        assert!(scored[0].0.starts_with("First"));
+    }
+
+    #[test]
+    fn test_segment_scored_confidence_penalizes_missing_year() {
+        let text = "\n[1] A reference with no detected year in it at all.\n[2] Another one from 2020 that has a year.\n[3] A third reference from 2021 also with a year.\n";
+        let scored = segment_references_scored(text);
+        assert_eq!(scored.len(), 3);
+        let (no_year, with_year) = (&scored[0], &scored[1]);
+        assert!(no_year.1 < with_year.1);
+    }
+
+    #[test]
+    fn test_segment_scored_confidence_penalizes_short_entries() {
+        let text = "\n[1] Short.\n[2] A much longer reference entry from 2020 that clears the minimum.\n[3] Another longer reference entry from 2021 that clears the minimum.\n";
+        let scored = segment_references_scored(text);
+        assert_eq!(scored.len(), 3);
+        assert!(scored[0].1 < scored[1].1);
+    }
+
+    #[test]
+    fn test_segment_scored_empty_input_returns_empty() {
+        assert!(segment_references_scored("").is_empty());
+    }
+
+    #[test]
+    fn test_segment_scored_confidence_in_bounds() {
+        let text = "1. First ref content here that is long enough, 2020.\n2. Second ref content here that is long enough, 2021.\n3. Third ref content that is long enough, 2022.\n";
+        let scored = segment_references_scored(text);
+        assert!(!scored.is_empty());
+        for (_, confidence) in &scored {
+            assert!(*confidence >= 0.0 && *confidence <= 1.0);
+        }
+    }
 }
@@ -0,0 +1,224 @@
+//! Renders this crate's report types into the JSON/CSV/HTML shapes the CLI
+//! and TUI reports are expected to embed: a "database performance" section
+//! built from [`DbPerformanceReport`], and per-reference rows that carry
+//! [`SkipInfo`]'s structured code and any [`FpReason`] alongside the title.
+
+use serde_json::json;
+
+use crate::types::{DbPerformanceReport, FpReason, ReportRef};
+
+/// JSON "database performance" section: one object per database, in the
+/// same order as `reports`.
+pub fn db_performance_section_json(reports: &[DbPerformanceReport]) -> serde_json::Value {
+    json!(
+        reports
+            .iter()
+            .map(|r| {
+                json!({
+                    "db_name": r.db_name,
+                    "requests": r.requests,
+                    "hits": r.hits,
+                    "not_found": r.not_found,
+                    "rate_limited": r.rate_limited,
+                    "timeouts": r.timeouts,
+                    "errors": r.errors,
+                    "retries": r.retries,
+                    "hit_rate": r.hit_rate(),
+                    "min_latency_ms": r.min_latency_ms,
+                    "median_latency_ms": r.median_latency_ms,
+                    "p95_latency_ms": r.p95_latency_ms,
+                })
+            })
+            .collect::<Vec<_>>()
+    )
+}
+
+/// HTML `<table>` rendering of the "database performance" section.
+pub fn db_performance_section_html(reports: &[DbPerformanceReport]) -> String {
+    let mut html = String::from(
+        "<table class=\"db-performance\">\n<thead><tr><th>Database</th><th>Requests</th>\
+         <th>Hits</th><th>Hit Rate</th><th>Rate Limited</th><th>Timeouts</th><th>Errors</th>\
+         <th>Median Latency</th><th>P95 Latency</th></tr></thead>\n<tbody>\n",
+    );
+    for r in reports {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.1}%</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&r.db_name),
+            r.requests,
+            r.hits,
+            r.hit_rate() * 100.0,
+            r.rate_limited,
+            r.timeouts,
+            r.errors,
+            latency_cell(r.median_latency_ms),
+            latency_cell(r.p95_latency_ms),
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n");
+    html
+}
+
+fn latency_cell(ms: Option<u64>) -> String {
+    ms.map_or_else(|| "—".to_string(), |ms| format!("{ms}ms"))
+}
+
+/// JSON for a single reference, including *why* it was skipped (the stable
+/// [`crate::types::SkipInfo::code`], not just the free-text reason) and any
+/// false-positive reason assigned to it.
+pub fn report_ref_json(r: &ReportRef) -> serde_json::Value {
+    json!({
+        "index": r.index,
+        "title": r.title,
+        "skip_reason": r.skip_info.as_ref().map(|s| s.reason.clone()),
+        "skip_code": r.skip_info.as_ref().and_then(|s| s.code),
+        "fp_reason": r.fp_reason.map(FpReason::as_str),
+    })
+}
+
+/// `report_ref_json`, applied to a whole batch and serialized as one JSON
+/// array.
+pub fn report_refs_json(refs: &[ReportRef]) -> serde_json::Value {
+    json!(refs.iter().map(report_ref_json).collect::<Vec<_>>())
+}
+
+/// CSV rendering of a batch of references, one row per reference.
+pub fn report_refs_csv(refs: &[ReportRef]) -> String {
+    let mut csv = String::from("index,title,skip_reason,skip_code,fp_reason\n");
+    for r in refs {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            r.index,
+            csv_escape(&r.title),
+            r.skip_info
+                .as_ref()
+                .map(|s| csv_escape(&s.reason))
+                .unwrap_or_default(),
+            r.skip_info.as_ref().and_then(|s| s.code).unwrap_or(""),
+            r.fp_reason.map(FpReason::as_str).unwrap_or(""),
+        ));
+    }
+    csv
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Escape the handful of characters that matter inside an HTML table cell.
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SkipInfo;
+
+    fn sample_report() -> DbPerformanceReport {
+        DbPerformanceReport {
+            db_name: "CrossRef".to_string(),
+            requests: 10,
+            hits: 7,
+            not_found: 2,
+            rate_limited: 1,
+            timeouts: 0,
+            errors: 0,
+            retries: 1,
+            min_latency_ms: Some(20),
+            median_latency_ms: Some(80),
+            p95_latency_ms: Some(300),
+        }
+    }
+
+    #[test]
+    fn db_performance_section_json_includes_hit_rate_and_db_name() {
+        let section = db_performance_section_json(&[sample_report()]);
+        let rows = section.as_array().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["db_name"], "CrossRef");
+        assert_eq!(rows[0]["requests"], 10);
+        assert_eq!(rows[0]["hit_rate"], 0.7);
+    }
+
+    #[test]
+    fn db_performance_section_html_renders_one_row_per_database() {
+        let html = db_performance_section_html(&[sample_report()]);
+        assert!(html.contains("CrossRef"));
+        assert!(html.contains("70.0%"));
+        assert!(html.contains("<table"));
+    }
+
+    #[test]
+    fn db_performance_section_html_escapes_db_name() {
+        let mut report = sample_report();
+        report.db_name = "<script>".to_string();
+        let html = db_performance_section_html(&[report]);
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn report_ref_json_carries_skip_code_and_fp_reason() {
+        let r = ReportRef {
+            index: 0,
+            title: "A Paper".to_string(),
+            skip_info: Some(SkipInfo {
+                reason: "CrossRef: request timed out".to_string(),
+                code: Some("timeout"),
+            }),
+            fp_reason: Some(FpReason::AllTimedOut),
+        };
+        let json = report_ref_json(&r);
+        assert_eq!(json["skip_code"], "timeout");
+        assert_eq!(json["fp_reason"], "all_timed_out");
+    }
+
+    #[test]
+    fn report_refs_csv_escapes_titles_with_commas() {
+        let refs = vec![ReportRef {
+            index: 0,
+            title: "Title, With Comma".to_string(),
+            skip_info: None,
+            fp_reason: None,
+        }];
+        let csv = report_refs_csv(&refs);
+        assert!(csv.contains("\"Title, With Comma\""));
+    }
+
+    #[test]
+    fn report_ref_json_has_null_skip_fields_when_not_skipped() {
+        let r = ReportRef {
+            index: 0,
+            title: "A Paper".to_string(),
+            skip_info: None,
+            fp_reason: None,
+        };
+        let json = report_ref_json(&r);
+        assert!(json["skip_reason"].is_null());
+        assert!(json["skip_code"].is_null());
+        assert!(json["fp_reason"].is_null());
+    }
+
+    #[test]
+    fn report_refs_csv_includes_the_skip_code_column() {
+        let refs = vec![ReportRef {
+            index: 0,
+            title: "A Paper".to_string(),
+            skip_info: Some(SkipInfo {
+                reason: "DBLP: rate limited".to_string(),
+                code: Some("rate_limited"),
+            }),
+            fp_reason: None,
+        }];
+        let csv = report_refs_csv(&refs);
+        let data_row = csv.lines().nth(1).unwrap();
+        assert_eq!(
+            data_row,
+            "0,A Paper,DBLP: rate limited,rate_limited,"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! Lightweight report types ([`types`]) and the JSON/HTML rendering
+//! ([`export`]) built on top of them.
+//!
+//! `ReportPaper` still has no real consumer: its `stats`/`results` fields
+//! borrow `hallucinator_core::{CheckStats, ValidationResult}`, neither of
+//! which exists yet in `hallucinator-core`'s current source — that's a
+//! pre-existing gap in this tree, not something [`export`] can work around.
+pub mod export;
+pub mod types;
+
+pub use types::*;
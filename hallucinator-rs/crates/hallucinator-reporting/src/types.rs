@@ -1,3 +1,5 @@
+use hallucinator_core::db::DbQueryError;
+use hallucinator_core::metrics::DbMetricsSnapshot;
 use hallucinator_core::{CheckStats, ValidationResult};
 
 /// Reason a user marked a reference as a false positive.
@@ -165,4 +167,149 @@ pub struct ReportRef {
 /// Information about why a reference was skipped.
 pub struct SkipInfo {
     pub reason: String,
+    /// Stable [`DbQueryError::code`] string for the failure that caused the
+    /// skip, when the skip came from a structured query error rather than a
+    /// manual user action — lets downstream tooling distinguish "all
+    /// databases timed out" from "genuinely not found" without parsing
+    /// `reason`.
+    pub code: Option<&'static str>,
+}
+
+impl SkipInfo {
+    /// Build skip info from a free-text reason with no associated error
+    /// code (e.g. a user-initiated skip).
+    pub fn from_reason(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            code: None,
+        }
+    }
+
+    /// Build skip info from a structured query failure, carrying its stable
+    /// code alongside a human-readable reason.
+    pub fn from_error(db_name: &str, error: &DbQueryError) -> Self {
+        Self {
+            reason: format!("{db_name}: {error}"),
+            code: Some(error.code()),
+        }
+    }
+}
+
+/// If every database attempted for a reference failed with the same
+/// [`DbQueryError::code`] of `"timeout"`, the reference likely exists but
+/// just couldn't be reached in time — promote this to an automatic
+/// [`FpReason::AllTimedOut`] instead of requiring the user to set it by
+/// hand. Returns `None` if `skip_codes` is empty or mixes failure kinds.
+pub fn auto_fp_reason(skip_codes: &[&str]) -> Option<FpReason> {
+    if !skip_codes.is_empty() && skip_codes.iter().all(|&c| c == "timeout") {
+        Some(FpReason::AllTimedOut)
+    } else {
+        None
+    }
+}
+
+/// One database's aggregated request/hit-rate/latency telemetry for a run,
+/// built from [`hallucinator_core::metrics::Metrics::snapshot_all`] and
+/// rendered as a "database performance" section in the JSON and HTML
+/// reports, so users auditing a large bibliography can see which databases
+/// actually contributed matches and which were slow or rate-limited.
+pub struct DbPerformanceReport {
+    pub db_name: String,
+    pub requests: u64,
+    pub hits: u64,
+    pub not_found: u64,
+    pub rate_limited: u64,
+    pub timeouts: u64,
+    pub errors: u64,
+    pub retries: u64,
+    pub min_latency_ms: Option<u64>,
+    pub median_latency_ms: Option<u64>,
+    pub p95_latency_ms: Option<u64>,
+}
+
+impl DbPerformanceReport {
+    /// Build a report row for `db_name` from its metrics snapshot.
+    pub fn new(db_name: impl Into<String>, snapshot: DbMetricsSnapshot) -> Self {
+        Self {
+            db_name: db_name.into(),
+            requests: snapshot.requests,
+            hits: snapshot.hits,
+            not_found: snapshot.not_found,
+            rate_limited: snapshot.rate_limited,
+            timeouts: snapshot.timeouts,
+            errors: snapshot.errors,
+            retries: snapshot.retries,
+            min_latency_ms: snapshot.min_latency_ms,
+            median_latency_ms: snapshot.median_latency_ms,
+            p95_latency_ms: snapshot.p95_latency_ms,
+        }
+    }
+
+    /// Fraction of requests that found a matching record, in `0.0..=1.0`.
+    pub fn hit_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.requests as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_fp_reason_is_none_for_empty_codes() {
+        assert_eq!(auto_fp_reason(&[]), None);
+    }
+
+    #[test]
+    fn auto_fp_reason_promotes_a_single_timeout() {
+        assert_eq!(auto_fp_reason(&["timeout"]), Some(FpReason::AllTimedOut));
+    }
+
+    #[test]
+    fn auto_fp_reason_is_none_for_mixed_codes() {
+        assert_eq!(auto_fp_reason(&["timeout", "rate_limited"]), None);
+    }
+
+    #[test]
+    fn auto_fp_reason_promotes_all_timeouts_across_multiple_databases() {
+        assert_eq!(
+            auto_fp_reason(&["timeout", "timeout", "timeout"]),
+            Some(FpReason::AllTimedOut)
+        );
+    }
+
+    #[test]
+    fn skip_info_from_error_carries_the_error_code_and_db_name() {
+        let skip = SkipInfo::from_error("CrossRef", &DbQueryError::Timeout);
+        assert_eq!(skip.code, Some("timeout"));
+        assert_eq!(skip.reason, "CrossRef: request timed out");
+    }
+
+    #[test]
+    fn skip_info_from_reason_has_no_code() {
+        let skip = SkipInfo::from_reason("user skipped");
+        assert_eq!(skip.code, None);
+        assert_eq!(skip.reason, "user skipped");
+    }
+
+    #[test]
+    fn db_performance_report_hit_rate_is_zero_with_no_requests() {
+        let report = DbPerformanceReport::new("DBLP", DbMetricsSnapshot::default());
+        assert_eq!(report.hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn db_performance_report_hit_rate_divides_hits_by_requests() {
+        let snapshot = DbMetricsSnapshot {
+            requests: 4,
+            hits: 3,
+            ..Default::default()
+        };
+        let report = DbPerformanceReport::new("DBLP", snapshot);
+        assert_eq!(report.hit_rate(), 0.75);
+    }
 }
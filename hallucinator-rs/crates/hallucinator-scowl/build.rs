@@ -0,0 +1,37 @@
+//! Compiles the embedded SCOWL word list and academic-terms supplement into
+//! a single sorted FST at build time, so [`ScowlDictionary::embedded`]
+//! (see `src/lib.rs`) can load it via `include_bytes!` instead of parsing
+//! the newline word lists on every startup.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn collect_words(content: &str, words: &mut BTreeSet<String>) {
+    for line in content.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        words.insert(line.to_lowercase());
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=data/wordlist.txt");
+    println!("cargo:rerun-if-changed=data/academic-terms.txt");
+
+    let mut words = BTreeSet::new();
+    collect_words(include_str!("data/wordlist.txt"), &mut words);
+    collect_words(include_str!("data/academic-terms.txt"), &mut words);
+
+    let mut builder = fst::SetBuilder::new(Vec::new()).expect("set builder");
+    for word in &words {
+        builder.insert(word).expect("words are inserted in sorted order");
+    }
+    let fst_bytes = builder.into_inner().expect("finish fst");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo during build scripts");
+    fs::write(Path::new(&out_dir).join("embedded.fst"), fst_bytes)
+        .expect("write embedded.fst to OUT_DIR");
+}
@@ -5,44 +5,71 @@
 //!
 //! # Loading Modes
 //!
-//! - **Embedded**: Load the compiled-in word list with [`ScowlDictionary::embedded()`]
-//! - **File-based**: Load from a file path with [`ScowlDictionary::from_file()`]
+//! - **Embedded**: Load the build-time-compiled word list with [`ScowlDictionary::embedded()`]
+//! - **Text file**: Parse a newline word list at runtime with [`ScowlDictionary::from_file()`]
+//! - **Compiled FST file**: Memory-map a pre-built `.fst` with [`ScowlDictionary::from_fst_file()`]
+//!
+//! All three are backed by the same ordered finite-state set rather than a
+//! `HashSet<String>` — a much smaller resident footprint than a full word
+//! list, O(len) lookups, and ordered/prefix iteration via
+//! [`ScowlDictionary::words_with_prefix`].
 //!
 //! # Integration with hallucinator-parsing
 //!
 //! This crate implements the [`Dictionary`] trait from `hallucinator-parsing`,
 //! allowing it to be used with [`hallucinator_parsing::text_processing::fix_hyphenation_with_dict`].
 
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::io;
 use std::path::Path;
 
+use fst::automaton::{Levenshtein, Str};
+use fst::{IntoStreamer, Set, Streamer};
 use hallucinator_parsing::Dictionary;
+use memmap2::Mmap;
+
+/// Backing storage for [`ScowlDictionary`]'s FST: bytes owned in memory,
+/// bytes borrowed from the binary's `.rodata` (the embedded compiled FST),
+/// or a memory-mapped file (for [`ScowlDictionary::from_fst_file`]).
+enum WordBytes {
+    Owned(Vec<u8>),
+    Static(&'static [u8]),
+    Mapped(Mmap),
+}
 
-/// A dictionary backed by SCOWL word lists.
-///
-/// Supports both embedded (compile-time) and file-based (runtime) loading.
+impl AsRef<[u8]> for WordBytes {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            WordBytes::Owned(bytes) => bytes.as_slice(),
+            WordBytes::Static(bytes) => bytes,
+            WordBytes::Mapped(mmap) => mmap.as_ref(),
+        }
+    }
+}
+
+/// A dictionary backed by an ordered finite-state set over SCOWL word lists.
 pub struct ScowlDictionary {
-    words: HashSet<String>,
+    fst: Set<WordBytes>,
 }
 
 impl ScowlDictionary {
-    /// Load the embedded SCOWL word list (size 70, ~160K words) plus academic terms.
+    /// Load the embedded SCOWL word list (size 70, ~160K words) plus academic
+    /// terms, from the FST compiled at build time by `build.rs` — no
+    /// runtime parsing of the newline word lists involved.
     ///
     /// This is the recommended way to use the dictionary for most cases.
-    /// Includes both the base SCOWL dictionary and a curated list of academic/technical
-    /// terms commonly found in research papers.
     pub fn embedded() -> Self {
-        let scowl = include_str!("../data/wordlist.txt");
-        let academic = include_str!("../data/academic-terms.txt");
-        Self::from_multiple(&[scowl, academic])
+        static EMBEDDED_FST: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embedded.fst"));
+        let fst = Set::new(WordBytes::Static(EMBEDDED_FST))
+            .expect("build.rs writes a valid FST to OUT_DIR/embedded.fst");
+        Self { fst }
     }
 
     /// Load dictionary from multiple string sources.
     ///
     /// Words from all sources are combined into a single dictionary.
     pub fn from_multiple(sources: &[&str]) -> Self {
-        let words = sources
+        let words: BTreeSet<String> = sources
             .iter()
             .flat_map(|content| {
                 content
@@ -51,12 +78,13 @@ impl ScowlDictionary {
                     .map(|l| l.to_lowercase())
             })
             .collect();
-        Self { words }
+        Self::from_words(words)
     }
 
-    /// Load dictionary from a file path.
+    /// Load dictionary from a file path containing a newline word list.
     ///
-    /// This allows loading custom or updated word lists at runtime.
+    /// This allows loading custom or updated word lists at runtime. Prefer
+    /// [`Self::from_fst_file`] for large lists already compiled to `.fst`.
     pub fn from_file(path: &Path) -> io::Result<Self> {
         let content = std::fs::read_to_string(path)?;
         Ok(Self::from_str(&content))
@@ -67,36 +95,141 @@ impl ScowlDictionary {
     /// Each line should contain one word. Empty lines and lines starting
     /// with '#' are ignored.
     pub fn from_str(content: &str) -> Self {
-        let words = content
+        let words: BTreeSet<String> = content
             .lines()
             .filter(|l| !l.is_empty() && !l.starts_with('#'))
             .map(|l| l.to_lowercase())
             .collect();
-        Self { words }
+        Self::from_words(words)
+    }
+
+    /// Load a dictionary from a pre-compiled `.fst` file, memory-mapping it
+    /// rather than reading it into resident memory. The file must contain an
+    /// ordered finite-state set over lowercased words, as produced by this
+    /// crate's `build.rs` or [`fst::SetBuilder`].
+    pub fn from_fst_file(path: &Path) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // Safety: the mapped file is treated as immutable for the lifetime
+        // of this dictionary; concurrent external writers would be unsound,
+        // the same caveat as any other memory-mapped file in this codebase.
+        let mmap = unsafe { Mmap::map(&file)? };
+        let fst = Set::new(WordBytes::Mapped(mmap))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self { fst })
+    }
+
+    /// Build a dictionary from an already-lowercased, deduplicated,
+    /// lexicographically sorted word set.
+    fn from_words(words: BTreeSet<String>) -> Self {
+        let fst = Set::from_iter(words)
+            .expect("words are deduplicated and lexicographically sorted by BTreeSet");
+        Self { fst }
     }
 
     /// Check if a word exists in the dictionary.
     ///
     /// The lookup is case-insensitive.
     pub fn contains(&self, word: &str) -> bool {
-        self.words.contains(&word.to_lowercase())
+        self.fst.contains(word.to_lowercase())
     }
 
     /// Return the number of words in the dictionary.
     pub fn len(&self) -> usize {
-        self.words.len()
+        self.fst.len()
     }
 
     /// Check if the dictionary is empty.
     pub fn is_empty(&self) -> bool {
-        self.words.is_empty()
+        self.fst.is_empty()
+    }
+
+    /// Enumerate every dictionary word starting with `prefix` (case-sensitive
+    /// — callers should lowercase `prefix` themselves, matching the rest of
+    /// this dictionary's case-insensitive-by-lowercasing convention).
+    ///
+    /// Useful when the hyphenation fixer has a truncated token and needs to
+    /// pick among plausible completions of its stem.
+    pub fn words_with_prefix(&self, prefix: &str) -> impl Iterator<Item = String> {
+        let automaton = Str::new(prefix).starts_with();
+        let mut stream = self.fst.search(automaton).into_stream();
+        let mut matches = Vec::new();
+        while let Some(key) = stream.next() {
+            if let Ok(word) = std::str::from_utf8(key) {
+                matches.push(word.to_string());
+            }
+        }
+        matches.into_iter()
     }
 }
 
 impl Dictionary for ScowlDictionary {
     fn contains(&self, word: &str) -> bool {
-        self.words.contains(&word.to_lowercase())
+        self.fst.contains(word.to_lowercase())
     }
+
+    fn correct(&self, word: &str, max_edits: u8) -> Option<String> {
+        let lower = word.to_lowercase();
+        let max_dist = heuristic_edit_distance(&lower, max_edits);
+        if max_dist == 0 {
+            return None;
+        }
+
+        let automaton = Levenshtein::new(&lower, max_dist).ok()?;
+        let mut stream = self.fst.search(automaton).into_stream();
+
+        let mut best: Option<(u32, String)> = None;
+        while let Some(key) = stream.next() {
+            let Ok(candidate) = std::str::from_utf8(key) else {
+                continue;
+            };
+            let dist = edit_distance(&lower, candidate);
+            let is_better = match &best {
+                None => true,
+                Some((best_dist, best_word)) => {
+                    dist < *best_dist || (dist == *best_dist && candidate < best_word.as_str())
+                }
+            };
+            if is_better {
+                best = Some((dist, candidate.to_string()));
+            }
+        }
+        best.map(|(_, word)| word)
+    }
+}
+
+/// Edit distance to tolerate for `word`, capped by the caller-supplied
+/// `max_edits`: short words (<5 chars) are too ambiguous to correct at all,
+/// medium words (5-8 chars) tolerate one edit, longer words tolerate two —
+/// common typo-tolerance heuristics.
+fn heuristic_edit_distance(word: &str, max_edits: u8) -> u32 {
+    let by_length = match word.chars().count() {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    };
+    by_length.min(max_edits as u32)
+}
+
+/// Standard Levenshtein edit distance, used only to rank FST candidates that
+/// already matched within the automaton's edit bound.
+fn edit_distance(a: &str, b: &str) -> u32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<u32> = (0..=b.len() as u32).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut prev_diag = row[0];
+        row[0] = i as u32 + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let temp = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev_diag
+            } else {
+                1 + prev_diag.min(row[j]).min(row[j + 1])
+            };
+            prev_diag = temp;
+        }
+    }
+    row[b.len()]
 }
 
 #[cfg(test)]
@@ -179,4 +312,66 @@ mod tests {
         assert!(dict.contains("world"));
         assert!(dict.contains("test"));
     }
+
+    #[test]
+    fn test_correct_snaps_dropped_letter_to_real_word() {
+        let dict = ScowlDictionary::embedded();
+        // "transformr" is one deletion away from "transformer".
+        assert_eq!(
+            dict.correct("transformr", 2),
+            Some("transformer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_correct_returns_none_for_short_words() {
+        let dict = ScowlDictionary::embedded();
+        // Under 5 characters, the heuristic tolerates zero edits.
+        assert_eq!(dict.correct("cta", 2), None);
+    }
+
+    #[test]
+    fn test_correct_returns_none_when_nothing_close() {
+        let dict = ScowlDictionary::embedded();
+        assert_eq!(dict.correct("zzzzzzzzzzzzzzzz", 1), None);
+    }
+
+    #[test]
+    fn test_correct_is_capped_by_max_edits() {
+        let dict = ScowlDictionary::from_str("kitten");
+        // "sitting" is 3 edits from "kitten" (s/k, e/i, +g), beyond both the
+        // length heuristic's 2-edit ceiling and this call's explicit cap.
+        assert_eq!(dict.correct("sitting", 1), None);
+    }
+
+    #[test]
+    fn test_words_with_prefix() {
+        let dict = ScowlDictionary::from_str("cat\ncar\ncard\ndog");
+        let mut matches: Vec<String> = dict.words_with_prefix("ca").collect();
+        matches.sort();
+        assert_eq!(matches, vec!["car", "card", "cat"]);
+    }
+
+    #[test]
+    fn test_words_with_prefix_no_matches() {
+        let dict = ScowlDictionary::from_str("cat\ndog");
+        assert_eq!(dict.words_with_prefix("zzz").count(), 0);
+    }
+
+    #[test]
+    fn test_from_fst_file_round_trips_through_a_compiled_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let fst_path = dir.path().join("words.fst");
+
+        let mut builder = fst::SetBuilder::new(Vec::new()).expect("set builder");
+        for word in ["apple", "banana", "cherry"] {
+            builder.insert(word).expect("sorted insert");
+        }
+        std::fs::write(&fst_path, builder.into_inner().expect("finish fst")).unwrap();
+
+        let dict = ScowlDictionary::from_fst_file(&fst_path).expect("load compiled fst");
+        assert_eq!(dict.len(), 3);
+        assert!(dict.contains("banana"));
+        assert!(!dict.contains("durian"));
+    }
 }
@@ -0,0 +1,7 @@
+//! Library surface for `hallucinator-tui`.
+//!
+//! Only [`persistence`] is exposed here: it's the one module with no
+//! dependency on the rest of this crate's (currently unimplemented) TUI
+//! model, so it doubles as the crash-resumable work-queue primitive that
+//! `hallucinator-cli`'s `check` loop builds `--resume` on top of.
+pub mod persistence;
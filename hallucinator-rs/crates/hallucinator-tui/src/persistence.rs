@@ -1,8 +1,7 @@
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::model::paper::RefState;
-use crate::model::queue::PaperState;
+use serde::{Deserialize, Serialize};
 
 /// Get the run directory for persisting results.
 /// Creates `~/.cache/hallucinator/runs/<timestamp>/` if it doesn't exist.
@@ -17,21 +16,281 @@ pub fn run_dir() -> Option<PathBuf> {
     Some(dir)
 }
 
-/// Persist results for a single paper to the run directory.
-///
-/// Uses the same rich JSON format as the export module so that saved results
-/// can be loaded back via `--load` or the file picker.
-pub fn save_paper_results(
-    run_dir: &std::path::Path,
-    paper_index: usize,
-    paper: &PaperState,
-    ref_states: &[RefState],
-) {
-    let out_path = run_dir.join(format!("paper_{}.json", paper_index));
-    let rs_slice: &[RefState] = ref_states;
-    let json = crate::export::export_json(&[paper], &[rs_slice]);
-
-    if let Ok(mut file) = std::fs::File::create(&out_path) {
-        let _ = file.write_all(json.as_bytes());
+/// How long a work item may sit `InFlight` before [`Manifest::reclaim_stale`]
+/// treats it as abandoned (e.g. the process was killed mid-query) and resets
+/// it to `Pending`.
+const STALE_IN_FLIGHT_SECS: i64 = 10 * 60;
+
+/// A single (paper, reference) unit of work tracked across a resumable run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkKey {
+    pub paper_index: usize,
+    pub ref_index: usize,
+}
+
+/// Where a [`WorkKey`] currently stands in a run's progress.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state")]
+pub enum WorkStatus {
+    /// Not yet attempted.
+    Pending,
+    /// Currently being queried. Carries a Unix timestamp (seconds) so a
+    /// future `resume_run` can tell a genuinely-stuck entry from one that's
+    /// just slow.
+    InFlight { started_at: i64 },
+    /// Finished successfully; results were saved via [`save_paper_results`].
+    Completed,
+    /// Deliberately not retried (e.g. every database timed out on it).
+    Skipped { reason: String },
+}
+
+/// A single entry in a run's work manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub key: WorkKey,
+    pub status: WorkStatus,
+}
+
+/// Crash-resumable work queue for a run: tracks every (paper, reference)
+/// pair as pending, in-flight, completed, or skipped, and persists to
+/// `manifest.json` in the run directory so an interrupted run can pick up
+/// where it left off instead of re-querying everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Build a fresh manifest with every (paper, reference) pair pending.
+    /// `ref_counts[i]` is the number of references for paper `i`.
+    pub fn seed(ref_counts: &[usize]) -> Self {
+        let entries = ref_counts
+            .iter()
+            .enumerate()
+            .flat_map(|(paper_index, &count)| {
+                (0..count).map(move |ref_index| ManifestEntry {
+                    key: WorkKey {
+                        paper_index,
+                        ref_index,
+                    },
+                    status: WorkStatus::Pending,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Entries that are still pending, in the order they were seeded.
+    pub fn pending(&self) -> impl Iterator<Item = WorkKey> + '_ {
+        self.entries.iter().filter_map(|e| match e.status {
+            WorkStatus::Pending => Some(e.key),
+            _ => None,
+        })
+    }
+
+    fn entry_mut(&mut self, key: WorkKey) -> Option<&mut ManifestEntry> {
+        self.entries.iter_mut().find(|e| e.key == key)
+    }
+
+    /// Current status of `key`, or `None` if it isn't in this manifest.
+    pub fn status(&self, key: WorkKey) -> Option<&WorkStatus> {
+        self.entries.iter().find(|e| e.key == key).map(|e| &e.status)
+    }
+
+    /// Mark `key` as currently being queried.
+    pub fn mark_in_flight(&mut self, key: WorkKey) {
+        if let Some(entry) = self.entry_mut(key) {
+            entry.status = WorkStatus::InFlight {
+                started_at: chrono::Local::now().timestamp(),
+            };
+        }
+    }
+
+    /// Mark `key` as done.
+    pub fn mark_completed(&mut self, key: WorkKey) {
+        if let Some(entry) = self.entry_mut(key) {
+            entry.status = WorkStatus::Completed;
+        }
+    }
+
+    /// Mark `key` as deliberately skipped, recording why.
+    pub fn mark_skipped(&mut self, key: WorkKey, reason: impl Into<String>) {
+        if let Some(entry) = self.entry_mut(key) {
+            entry.status = WorkStatus::Skipped {
+                reason: reason.into(),
+            };
+        }
+    }
+
+    /// Reset any entry that's been `InFlight` for longer than
+    /// `STALE_IN_FLIGHT_SECS` back to `Pending` — the process that claimed it
+    /// likely crashed or was killed before finishing.
+    pub fn reclaim_stale(&mut self) {
+        let now = chrono::Local::now().timestamp();
+        for entry in &mut self.entries {
+            if let WorkStatus::InFlight { started_at } = entry.status {
+                if now - started_at >= STALE_IN_FLIGHT_SECS {
+                    entry.status = WorkStatus::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// Path of a run's manifest file.
+fn manifest_path(run_dir: &Path) -> PathBuf {
+    run_dir.join("manifest.json")
+}
+
+/// Persist `manifest` to `run_dir/manifest.json`.
+pub fn save_manifest(run_dir: &Path, manifest: &Manifest) {
+    if let Ok(json) = serde_json::to_string_pretty(manifest) {
+        if let Ok(mut file) = std::fs::File::create(manifest_path(run_dir)) {
+            let _ = file.write_all(json.as_bytes());
+        }
+    }
+}
+
+/// Load a run's manifest, if one was saved.
+pub fn load_manifest(run_dir: &Path) -> Option<Manifest> {
+    let content = std::fs::read_to_string(manifest_path(run_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Resume a previously-started run: loads its manifest and reclaims any
+/// `InFlight` entries left stale by a crash or kill, so they're retried
+/// instead of silently dropped. Returns `None` if `run_dir` has no manifest
+/// (e.g. it predates this feature, or the run never got far enough to save
+/// one).
+pub fn resume_run(run_dir: &Path) -> Option<Manifest> {
+    let mut manifest = load_manifest(run_dir)?;
+    manifest.reclaim_stale();
+    save_manifest(run_dir, &manifest);
+    Some(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_marks_every_pair_pending() {
+        let manifest = Manifest::seed(&[2, 1]);
+        let pending: Vec<WorkKey> = manifest.pending().collect();
+        assert_eq!(pending.len(), 3);
+        assert!(pending.contains(&WorkKey {
+            paper_index: 0,
+            ref_index: 0
+        }));
+        assert!(pending.contains(&WorkKey {
+            paper_index: 1,
+            ref_index: 0
+        }));
+    }
+
+    #[test]
+    fn mark_completed_removes_entry_from_pending() {
+        let mut manifest = Manifest::seed(&[2]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        manifest.mark_completed(key);
+        let pending: Vec<WorkKey> = manifest.pending().collect();
+        assert_eq!(pending.len(), 1);
+        assert!(!pending.contains(&key));
+    }
+
+    #[test]
+    fn status_reports_completed_after_mark_completed() {
+        let mut manifest = Manifest::seed(&[1]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        assert_eq!(manifest.status(key), Some(&WorkStatus::Pending));
+        manifest.mark_completed(key);
+        assert_eq!(manifest.status(key), Some(&WorkStatus::Completed));
+    }
+
+    #[test]
+    fn mark_skipped_records_reason() {
+        let mut manifest = Manifest::seed(&[1]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        manifest.mark_skipped(key, "all databases timed out");
+        match &manifest.entries[0].status {
+            WorkStatus::Skipped { reason } => assert_eq!(reason, "all databases timed out"),
+            other => panic!("expected Skipped, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reclaim_stale_resets_old_in_flight_entries_to_pending() {
+        let mut manifest = Manifest::seed(&[1]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        manifest.entry_mut(key).unwrap().status = WorkStatus::InFlight {
+            started_at: chrono::Local::now().timestamp() - STALE_IN_FLIGHT_SECS - 1,
+        };
+        manifest.reclaim_stale();
+        assert_eq!(manifest.pending().collect::<Vec<_>>(), vec![key]);
+    }
+
+    #[test]
+    fn reclaim_stale_leaves_fresh_in_flight_entries_alone() {
+        let mut manifest = Manifest::seed(&[1]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        manifest.mark_in_flight(key);
+        manifest.reclaim_stale();
+        assert!(manifest.pending().collect::<Vec<_>>().is_empty());
+    }
+
+    #[test]
+    fn save_and_load_manifest_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = Manifest::seed(&[1, 1]);
+        manifest.mark_completed(WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        });
+        save_manifest(dir.path(), &manifest);
+
+        let loaded = load_manifest(dir.path()).unwrap();
+        assert_eq!(loaded.pending().collect::<Vec<_>>().len(), 1);
+    }
+
+    #[test]
+    fn resume_run_reclaims_stale_entries_and_persists_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = Manifest::seed(&[1]);
+        let key = WorkKey {
+            paper_index: 0,
+            ref_index: 0,
+        };
+        manifest.entry_mut(key).unwrap().status = WorkStatus::InFlight {
+            started_at: chrono::Local::now().timestamp() - STALE_IN_FLIGHT_SECS - 1,
+        };
+        save_manifest(dir.path(), &manifest);
+
+        let resumed = resume_run(dir.path()).unwrap();
+        assert_eq!(resumed.pending().collect::<Vec<_>>(), vec![key]);
+
+        // The reclaim was persisted, not just returned in memory.
+        let reloaded = load_manifest(dir.path()).unwrap();
+        assert_eq!(reloaded.pending().collect::<Vec<_>>(), vec![key]);
+    }
+
+    #[test]
+    fn resume_run_returns_none_without_a_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(resume_run(dir.path()).is_none());
     }
 }
@@ -0,0 +1,273 @@
+//! Runtime-loadable color themes for the TUI.
+//!
+//! Themes are TOML files in `<config_dir>/hallucinator/themes/<name>.toml`,
+//! each declaring entries like `active = "#00ffd7"`. A value may be a hex
+//! string (`#rrggbb`), a named color (`cyan`), or a 256-palette index
+//! (`"165"`). Missing or unparsable keys fall back to [`Theme::default`]
+//! entry by entry, so a theme file only needs to override what it wants to
+//! change. The active theme is re-resolved (via [`Theme::load`]) whenever
+//! the user switches it from the `,`-menu selector, so the whole UI
+//! repaints with the new palette.
+
+use std::path::PathBuf;
+
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// Name of the built-in fallback theme. Always present in
+/// [`available_themes`], even if no theme files exist on disk.
+pub const DEFAULT_THEME_NAME: &str = "default";
+
+/// Built-in 12-stop rainbow used for the splash effect when a theme doesn't
+/// define its own `rainbow` entries.
+const DEFAULT_RAINBOW: [(u8, u8, u8); 12] = [
+    (255, 0, 0),   // Red
+    (255, 127, 0), // Orange
+    (255, 255, 0), // Yellow
+    (127, 255, 0), // Chartreuse
+    (0, 255, 0),   // Green
+    (0, 255, 127), // Spring
+    (0, 255, 255), // Cyan
+    (0, 127, 255), // Azure
+    (0, 0, 255),   // Blue
+    (127, 0, 255), // Violet
+    (255, 0, 255), // Magenta
+    (255, 0, 127), // Rose
+];
+
+/// Resolved color palette used throughout the TUI.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub text: Color,
+    pub dim: Color,
+    pub active: Color,
+    pub border: Color,
+    /// 12-stop palette for the splash rainbow effect (see
+    /// [`banner::rainbow_line`](crate::view::banner)). Always exactly 12
+    /// entries — falls back to [`DEFAULT_RAINBOW`] when a theme omits it or
+    /// supplies the wrong number of stops.
+    pub rainbow: Vec<(u8, u8, u8)>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            text: Color::White,
+            dim: Color::DarkGray,
+            active: Color::Cyan,
+            border: Color::Gray,
+            rainbow: DEFAULT_RAINBOW.to_vec(),
+        }
+    }
+}
+
+/// Raw TOML shape for a theme file. Every field is optional so partial
+/// overrides fall back to [`Theme::default`] field by field.
+#[derive(Debug, Deserialize, Default)]
+struct ThemeSpec {
+    text: Option<String>,
+    dim: Option<String>,
+    active: Option<String>,
+    border: Option<String>,
+    rainbow: Option<Vec<String>>,
+}
+
+impl Theme {
+    /// Load a named theme from the config directory.
+    ///
+    /// Falls back to [`Theme::default`] if the theme file doesn't exist,
+    /// fails to parse, or is missing a key — unknown keys just keep the
+    /// default value for that slot rather than erroring out.
+    pub fn load(name: &str) -> Self {
+        match read_theme_spec(name) {
+            Some(spec) => Self::from_spec(&spec),
+            None => Self::default(),
+        }
+    }
+
+    fn from_spec(spec: &ThemeSpec) -> Self {
+        let default = Self::default();
+        let rainbow = spec
+            .rainbow
+            .as_ref()
+            .map(|stops| stops.iter().filter_map(|s| parse_rgb(s)).collect::<Vec<_>>())
+            .filter(|stops| stops.len() == DEFAULT_RAINBOW.len())
+            .unwrap_or(default.rainbow.clone());
+
+        Self {
+            text: spec.text.as_deref().and_then(parse_color).unwrap_or(default.text),
+            dim: spec.dim.as_deref().and_then(parse_color).unwrap_or(default.dim),
+            active: spec
+                .active
+                .as_deref()
+                .and_then(parse_color)
+                .unwrap_or(default.active),
+            border: spec
+                .border
+                .as_deref()
+                .and_then(parse_color)
+                .unwrap_or(default.border),
+            rainbow,
+        }
+    }
+}
+
+/// Parse a theme color value: `#rrggbb` hex, a named ANSI color, or a
+/// 256-palette index given as a bare number.
+fn parse_color(s: &str) -> Option<Color> {
+    if let Some((r, g, b)) = parse_rgb(s) {
+        return Some(Color::Rgb(r, g, b));
+    }
+    if let Ok(index) = s.parse::<u8>() {
+        return Some(Color::Indexed(index));
+    }
+    named_color(s)
+}
+
+/// Parse a `#rrggbb` or `#rgb` hex string into an RGB triple.
+fn parse_rgb(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.strip_prefix('#')?;
+    match hex.len() {
+        6 => {
+            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+            Some((r, g, b))
+        }
+        3 => {
+            let r = u8::from_str_radix(&hex[0..1].repeat(2), 16).ok()?;
+            let g = u8::from_str_radix(&hex[1..2].repeat(2), 16).ok()?;
+            let b = u8::from_str_radix(&hex[2..3].repeat(2), 16).ok()?;
+            Some((r, g, b))
+        }
+        _ => None,
+    }
+}
+
+/// Map common named colors (as editors accept them in theme files) to
+/// `ratatui` colors.
+fn named_color(s: &str) -> Option<Color> {
+    match s.to_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" | "grey" => Some(Color::Gray),
+        "darkgray" | "darkgrey" => Some(Color::DarkGray),
+        "lightred" => Some(Color::LightRed),
+        "lightgreen" => Some(Color::LightGreen),
+        "lightyellow" => Some(Color::LightYellow),
+        "lightblue" => Some(Color::LightBlue),
+        "lightmagenta" => Some(Color::LightMagenta),
+        "lightcyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+/// Directory themes are loaded from: `<config_dir>/hallucinator/themes/`.
+fn themes_dir() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join("hallucinator").join("themes"))
+}
+
+fn read_theme_spec(name: &str) -> Option<ThemeSpec> {
+    if name == DEFAULT_THEME_NAME {
+        return None;
+    }
+    let path = themes_dir()?.join(format!("{name}.toml"));
+    let content = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// List available theme names, sorted, with [`DEFAULT_THEME_NAME`] always
+/// first — even if `themes_dir()` doesn't exist or is empty.
+pub fn available_themes() -> Vec<String> {
+    let mut names = vec![DEFAULT_THEME_NAME.to_string()];
+
+    if let Some(dir) = themes_dir() {
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            let mut found: Vec<String> = entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().extension().is_some_and(|ext| ext == "toml"))
+                .filter_map(|e| e.path().file_stem().map(|s| s.to_string_lossy().into_owned()))
+                .filter(|n| n != DEFAULT_THEME_NAME)
+                .collect();
+            found.sort();
+            names.extend(found);
+        }
+    }
+    names
+}
+
+/// Advance to the next theme name in [`available_themes`], wrapping around.
+/// Backs the `,`-menu theme selector.
+pub fn cycle_theme(current: &str) -> String {
+    let names = available_themes();
+    let idx = names.iter().position(|n| n == current).unwrap_or(0);
+    names[(idx + 1) % names.len()].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme_has_sane_colors() {
+        let theme = Theme::default();
+        assert_eq!(theme.text, Color::White);
+        assert_eq!(theme.rainbow.len(), 12);
+    }
+
+    #[test]
+    fn test_parse_rgb_hex() {
+        assert_eq!(parse_rgb("#00ffd7"), Some((0, 255, 215)));
+        assert_eq!(parse_rgb("#0f0"), Some((0, 255, 0)));
+        assert_eq!(parse_rgb("not-a-color"), None);
+    }
+
+    #[test]
+    fn test_parse_color_named_and_indexed() {
+        assert_eq!(parse_color("cyan"), Some(Color::Cyan));
+        assert_eq!(parse_color("165"), Some(Color::Indexed(165)));
+        assert_eq!(parse_color("nonsense"), None);
+    }
+
+    #[test]
+    fn test_from_spec_falls_back_to_default_for_missing_keys() {
+        let spec = ThemeSpec {
+            active: Some("#00ffd7".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::from_spec(&spec);
+        assert_eq!(theme.active, Color::Rgb(0, 255, 215));
+        assert_eq!(theme.text, Theme::default().text);
+        assert_eq!(theme.rainbow, DEFAULT_RAINBOW.to_vec());
+    }
+
+    #[test]
+    fn test_from_spec_rejects_wrong_length_rainbow() {
+        let spec = ThemeSpec {
+            rainbow: Some(vec!["#ff0000".to_string(), "#00ff00".to_string()]),
+            ..Default::default()
+        };
+        let theme = Theme::from_spec(&spec);
+        assert_eq!(theme.rainbow, DEFAULT_RAINBOW.to_vec());
+    }
+
+    #[test]
+    fn test_available_themes_always_includes_default() {
+        let names = available_themes();
+        assert_eq!(names[0], DEFAULT_THEME_NAME);
+    }
+
+    #[test]
+    fn test_cycle_theme_wraps_around() {
+        // With no theme files on disk, only "default" is available, so
+        // cycling from it should return to itself.
+        let next = cycle_theme(DEFAULT_THEME_NAME);
+        assert_eq!(next, DEFAULT_THEME_NAME);
+    }
+}
@@ -20,22 +20,6 @@ const GLASS: &[&str] = &["  ╭─────╮ ", "  │  ·  │ ", "  ╰
 
 const GLASS_WIDTH: u16 = 11;
 
-// 12-stop rainbow palette for the trippy splash effect
-const RAINBOW: &[(u8, u8, u8)] = &[
-    (255, 0, 0),   // Red
-    (255, 127, 0), // Orange
-    (255, 255, 0), // Yellow
-    (127, 255, 0), // Chartreuse
-    (0, 255, 0),   // Green
-    (0, 255, 127), // Spring
-    (0, 255, 255), // Cyan
-    (0, 127, 255), // Azure
-    (0, 0, 255),   // Blue
-    (127, 0, 255), // Violet
-    (255, 0, 255), // Magenta
-    (255, 0, 127), // Rose
-];
-
 // Tip strings — the "Pro-tip: " prefix is stripped when displayed in the pane
 // (the pane header already reads "Pro-tips"), but kept for narrow-terminal fallback.
 const TIPS: &[&str] = &[
@@ -54,13 +38,16 @@ const TIPS: &[&str] = &[
 /// Build a single logo line with flowing rainbow colors.
 /// Block characters (█▀▄) get full brightness; light shade (░) gets dimmed
 /// for contrast, creating a psychedelic wave that shifts each tick.
-fn rainbow_line(text: &str, row: usize, tick: usize) -> Line<'static> {
+/// `rainbow` is the active theme's 12-stop palette (`theme.rainbow`), so
+/// community themes can restyle the splash effect instead of being stuck
+/// with the built-in colors.
+fn rainbow_line(text: &str, row: usize, tick: usize, rainbow: &[(u8, u8, u8)]) -> Line<'static> {
     let spans: Vec<Span> = text
         .chars()
         .enumerate()
         .map(|(col, ch)| {
-            let idx = (col / 2 + row * 3 + tick) % RAINBOW.len();
-            let (r, g, b) = RAINBOW[idx];
+            let idx = (col / 2 + row * 3 + tick) % rainbow.len();
+            let (r, g, b) = rainbow[idx];
             let color = if ch == '░' {
                 // Dim background shade — still tinted but low brightness
                 Color::Rgb(r / 5, g / 5, b / 5)
@@ -101,7 +88,7 @@ pub fn render(f: &mut Frame, theme: &Theme, tick: usize) {
 
     if show_logo {
         for (row, art_line) in LOGO.iter().enumerate() {
-            lines.push(rainbow_line(art_line, row, tick));
+            lines.push(rainbow_line(art_line, row, tick, &theme.rainbow));
         }
     }
 
@@ -0,0 +1,145 @@
+//! Workload-driven benchmarking for DBLP build throughput and query latency.
+//!
+//! A workload is a small JSON file naming a fixed `.nt.gz`/`.nt.zst` fixture
+//! to build from and a list of title queries to run against the resulting
+//! database. This hooks the existing [`hallucinator_dblp::BuildProgress`]
+//! callbacks to measure build wall-time and lines/sec, then times
+//! [`hallucinator_dblp::db::search_titles`] to report per-query p50/p95
+//! latency and match counts. Results are printed as JSON so runs can be
+//! diffed over time or posted to a dashboard.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A workload file describing one benchmark scenario.
+#[derive(Debug, Deserialize)]
+struct Workload {
+    name: String,
+    /// Path to a `.nt.gz`/`.nt.zst` fixture, relative to the workload file.
+    fixture: PathBuf,
+    queries: Vec<String>,
+    #[serde(default = "default_query_iterations")]
+    query_iterations: usize,
+}
+
+fn default_query_iterations() -> usize {
+    20
+}
+
+/// Machine-readable result of running a workload.
+#[derive(Debug, Serialize)]
+struct WorkloadResult {
+    name: String,
+    build_wall_time_secs: f64,
+    lines_processed: u64,
+    lines_per_sec: f64,
+    publications: u64,
+    authors: u64,
+    queries: Vec<QueryResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct QueryResult {
+    query: String,
+    iterations: usize,
+    matches: usize,
+    p50_micros: f64,
+    p95_micros: f64,
+}
+
+/// Run the workload at `workload_path` and print its [`WorkloadResult`] as
+/// pretty-printed JSON.
+pub fn run(workload_path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(workload_path)
+        .with_context(|| format!("reading workload file {}", workload_path.display()))?;
+    let workload: Workload = serde_json::from_str(&content)
+        .with_context(|| format!("parsing workload file {}", workload_path.display()))?;
+
+    let fixture_dir = workload_path.parent().unwrap_or_else(|| Path::new("."));
+    let fixture_path = fixture_dir.join(&workload.fixture);
+
+    let tmp_dir = tempfile::tempdir().context("creating scratch dir for bench database")?;
+    let db_path = tmp_dir.path().join("bench.db");
+
+    let lines_processed = AtomicU64::new(0);
+
+    let start = Instant::now();
+    hallucinator_dblp::build_from_file(&db_path, &fixture_path, |event| {
+        if let hallucinator_dblp::BuildProgress::Parsing {
+            lines_processed: lp,
+            ..
+        } = event
+        {
+            lines_processed.store(lp, Ordering::Relaxed);
+        }
+    })
+    .with_context(|| format!("building database from fixture {}", fixture_path.display()))?;
+    let build_wall_time = start.elapsed().as_secs_f64();
+
+    let conn = rusqlite::Connection::open(&db_path).context("opening bench database")?;
+    let (publications, authors, _) =
+        hallucinator_dblp::db::get_counts(&conn).context("reading bench database counts")?;
+
+    let mut queries = Vec::with_capacity(workload.queries.len());
+    for query in &workload.queries {
+        queries.push(bench_query(&conn, query, workload.query_iterations)?);
+    }
+
+    let lines = lines_processed.load(Ordering::Relaxed);
+    let result = WorkloadResult {
+        name: workload.name,
+        build_wall_time_secs: build_wall_time,
+        lines_processed: lines,
+        lines_per_sec: if build_wall_time > 0.0 {
+            lines as f64 / build_wall_time
+        } else {
+            0.0
+        },
+        publications: publications as u64,
+        authors: authors as u64,
+        queries,
+    };
+
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
+
+/// Time `iterations` runs of `query` and summarize as p50/p95 latency.
+fn bench_query(
+    conn: &rusqlite::Connection,
+    query: &str,
+    iterations: usize,
+) -> Result<QueryResult> {
+    let mut micros = Vec::with_capacity(iterations);
+    let mut matches = 0;
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let results = hallucinator_dblp::db::search_titles(conn, query, 10)
+            .context("running bench query")?;
+        micros.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+        matches = results.len();
+    }
+
+    micros.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(QueryResult {
+        query: query.to_string(),
+        iterations,
+        matches,
+        p50_micros: percentile(&micros, 0.50),
+        p95_micros: percentile(&micros, 0.95),
+    })
+}
+
+/// Nearest-rank percentile over an already-sorted sample.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let idx = (((sorted.len() - 1) as f64) * p).round() as usize;
+    sorted[idx]
+}
@@ -0,0 +1,35 @@
+//! Developer tasks for this workspace, run via `cargo xtask <task>`.
+//!
+//! Currently just `bench`, which drives a recorded workload file through the
+//! DBLP build/query pipeline and prints machine-readable JSON results. See
+//! [`bench`] and `workloads/dblp-small.json` for the workload format.
+
+mod bench;
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "xtask")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Build a DBLP database from a workload's fixture and benchmark build
+    /// throughput plus title-search latency against the result.
+    Bench {
+        /// Path to a workload JSON file, e.g. `workloads/dblp-small.json`.
+        workload: PathBuf,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Bench { workload } => bench::run(&workload),
+    }
+}